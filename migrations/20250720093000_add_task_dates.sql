@@ -0,0 +1,3 @@
+-- Add due and scheduled dates to tasks
+ALTER TABLE tasks ADD COLUMN due TEXT;
+ALTER TABLE tasks ADD COLUMN scheduled TEXT;
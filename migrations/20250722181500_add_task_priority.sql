@@ -0,0 +1,2 @@
+-- Add priority to tasks, 0 (none) to 4 (urgent)
+ALTER TABLE tasks ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
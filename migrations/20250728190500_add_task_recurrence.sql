@@ -0,0 +1,2 @@
+-- Add recurrence rule to tasks, stored as an RRULE
+ALTER TABLE tasks ADD COLUMN recurrence TEXT;
@@ -0,0 +1,37 @@
+-- Full-text index over task titles and descriptions
+CREATE VIRTUAL TABLE IF NOT EXISTS tasks_fts USING fts5(
+  title,
+  description,
+  content = 'tasks',
+  content_rowid = 'id'
+);
+
+-- Index the tasks that already exist
+INSERT INTO tasks_fts (tasks_fts) VALUES ('rebuild');
+
+-- Triggers to keep the index in sync with tasks
+CREATE TRIGGER IF NOT EXISTS tasks_fts_after_insert
+AFTER INSERT ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO tasks_fts (rowid, title, description)
+  VALUES (NEW.id, NEW.title, NEW.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_after_delete
+AFTER DELETE ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO tasks_fts (tasks_fts, rowid, title, description)
+  VALUES ('delete', OLD.id, OLD.title, OLD.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_after_update
+AFTER UPDATE OF title, description ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO tasks_fts (tasks_fts, rowid, title, description)
+  VALUES ('delete', OLD.id, OLD.title, OLD.description);
+  INSERT INTO tasks_fts (rowid, title, description)
+  VALUES (NEW.id, NEW.title, NEW.description);
+END;
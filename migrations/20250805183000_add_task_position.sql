@@ -0,0 +1,16 @@
+-- Manual order of a task among its siblings
+ALTER TABLE tasks ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+-- Keep the current order of existing tasks
+UPDATE tasks SET position = id;
+
+-- New tasks go after every existing task
+CREATE TRIGGER IF NOT EXISTS tasks_after_insert_position
+AFTER INSERT ON tasks
+FOR EACH ROW
+WHEN NEW.position = 0
+BEGIN
+  UPDATE tasks
+  SET position = (SELECT MAX(position) + 1 FROM tasks)
+  WHERE id = NEW.id;
+END;
@@ -0,0 +1,2 @@
+-- Whether the subtasks of a task are hidden in the TUI
+ALTER TABLE tasks ADD COLUMN folded INTEGER NOT NULL DEFAULT false;
@@ -0,0 +1,140 @@
+-- Journal of the SQL statements that undo each change, see https://www.sqlite.org/undoredo.html
+CREATE TABLE IF NOT EXISTS undolog (
+  seq INTEGER PRIMARY KEY,
+  sql TEXT NOT NULL
+) STRICT;
+
+-- Undoable steps, each one a range of statements in undolog
+CREATE TABLE IF NOT EXISTS undo_steps (
+  id INTEGER PRIMARY KEY,
+  kind TEXT NOT NULL CHECK (kind IN ('undo', 'redo')),
+  first_seq INTEGER NOT NULL,
+  last_seq INTEGER NOT NULL
+) STRICT;
+
+-- Tasks
+CREATE TRIGGER IF NOT EXISTS tasks_undo_insert
+AFTER INSERT ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES ('DELETE FROM tasks WHERE id = ' || NEW.id);
+END;
+
+-- Folding only changes the view, so it is not undoable
+CREATE TRIGGER IF NOT EXISTS tasks_undo_update
+AFTER UPDATE OF
+  title, description, done, project_id, parent_id, sub_task_ids,
+  due, scheduled, priority, recurrence, position
+ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'UPDATE tasks SET'
+    || ' title = ' || quote(OLD.title)
+    || ', description = ' || quote(OLD.description)
+    || ', done = ' || quote(OLD.done)
+    || ', project_id = ' || quote(OLD.project_id)
+    || ', parent_id = ' || quote(OLD.parent_id)
+    || ', sub_task_ids = ' || quote(OLD.sub_task_ids)
+    || ', due = ' || quote(OLD.due)
+    || ', scheduled = ' || quote(OLD.scheduled)
+    || ', priority = ' || quote(OLD.priority)
+    || ', recurrence = ' || quote(OLD.recurrence)
+    || ', position = ' || quote(OLD.position)
+    || ' WHERE id = ' || OLD.id
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_undo_delete
+AFTER DELETE ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'INSERT INTO tasks (id, title, description, done, project_id, parent_id, sub_task_ids,'
+    || ' due, scheduled, priority, recurrence, position, folded) VALUES ('
+    || OLD.id
+    || ', ' || quote(OLD.title)
+    || ', ' || quote(OLD.description)
+    || ', ' || quote(OLD.done)
+    || ', ' || quote(OLD.project_id)
+    || ', ' || quote(OLD.parent_id)
+    || ', ' || quote(OLD.sub_task_ids)
+    || ', ' || quote(OLD.due)
+    || ', ' || quote(OLD.scheduled)
+    || ', ' || quote(OLD.priority)
+    || ', ' || quote(OLD.recurrence)
+    || ', ' || quote(OLD.position)
+    || ', ' || quote(OLD.folded)
+    || ')'
+  );
+END;
+
+-- Projects
+CREATE TRIGGER IF NOT EXISTS projects_undo_insert
+AFTER INSERT ON projects
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES ('DELETE FROM projects WHERE id = ' || NEW.id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS projects_undo_update
+AFTER UPDATE ON projects
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'UPDATE projects SET name = ' || quote(OLD.name) || ' WHERE id = ' || OLD.id
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS projects_undo_delete
+AFTER DELETE ON projects
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'INSERT INTO projects (id, name) VALUES (' || OLD.id || ', ' || quote(OLD.name) || ')'
+  );
+END;
+
+-- Tags
+CREATE TRIGGER IF NOT EXISTS tags_undo_insert
+AFTER INSERT ON tags
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES ('DELETE FROM tags WHERE id = ' || NEW.id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tags_undo_update
+AFTER UPDATE ON tags
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'UPDATE tags SET name = ' || quote(OLD.name) || ' WHERE id = ' || OLD.id
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS tags_undo_delete
+AFTER DELETE ON tags
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'INSERT INTO tags (id, name) VALUES (' || OLD.id || ', ' || quote(OLD.name) || ')'
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_tags_undo_insert
+AFTER INSERT ON task_tags
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'DELETE FROM task_tags WHERE task_id = ' || NEW.task_id || ' AND tag_id = ' || NEW.tag_id
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_tags_undo_delete
+AFTER DELETE ON task_tags
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'INSERT INTO task_tags (task_id, tag_id) VALUES (' || OLD.task_id || ', ' || OLD.tag_id || ')'
+  );
+END;
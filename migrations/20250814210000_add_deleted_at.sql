@@ -0,0 +1,80 @@
+-- Deleted tasks and projects stay in the trash until they are purged
+ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+ALTER TABLE projects ADD COLUMN deleted_at TEXT;
+
+-- The undo log has to restore the new columns too
+DROP TRIGGER IF EXISTS tasks_undo_update;
+DROP TRIGGER IF EXISTS tasks_undo_delete;
+DROP TRIGGER IF EXISTS projects_undo_update;
+DROP TRIGGER IF EXISTS projects_undo_delete;
+
+CREATE TRIGGER IF NOT EXISTS tasks_undo_update
+AFTER UPDATE OF
+  title, description, done, project_id, parent_id, sub_task_ids,
+  due, scheduled, priority, recurrence, position, deleted_at
+ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'UPDATE tasks SET'
+    || ' title = ' || quote(OLD.title)
+    || ', description = ' || quote(OLD.description)
+    || ', done = ' || quote(OLD.done)
+    || ', project_id = ' || quote(OLD.project_id)
+    || ', parent_id = ' || quote(OLD.parent_id)
+    || ', sub_task_ids = ' || quote(OLD.sub_task_ids)
+    || ', due = ' || quote(OLD.due)
+    || ', scheduled = ' || quote(OLD.scheduled)
+    || ', priority = ' || quote(OLD.priority)
+    || ', recurrence = ' || quote(OLD.recurrence)
+    || ', position = ' || quote(OLD.position)
+    || ', deleted_at = ' || quote(OLD.deleted_at)
+    || ' WHERE id = ' || OLD.id
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_undo_delete
+AFTER DELETE ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'INSERT INTO tasks (id, title, description, done, project_id, parent_id, sub_task_ids,'
+    || ' due, scheduled, priority, recurrence, position, folded, deleted_at) VALUES ('
+    || OLD.id
+    || ', ' || quote(OLD.title)
+    || ', ' || quote(OLD.description)
+    || ', ' || quote(OLD.done)
+    || ', ' || quote(OLD.project_id)
+    || ', ' || quote(OLD.parent_id)
+    || ', ' || quote(OLD.sub_task_ids)
+    || ', ' || quote(OLD.due)
+    || ', ' || quote(OLD.scheduled)
+    || ', ' || quote(OLD.priority)
+    || ', ' || quote(OLD.recurrence)
+    || ', ' || quote(OLD.position)
+    || ', ' || quote(OLD.folded)
+    || ', ' || quote(OLD.deleted_at)
+    || ')'
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS projects_undo_update
+AFTER UPDATE ON projects
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'UPDATE projects SET name = ' || quote(OLD.name)
+    || ', deleted_at = ' || quote(OLD.deleted_at)
+    || ' WHERE id = ' || OLD.id
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS projects_undo_delete
+AFTER DELETE ON projects
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'INSERT INTO projects (id, name, deleted_at) VALUES ('
+    || OLD.id || ', ' || quote(OLD.name) || ', ' || quote(OLD.deleted_at) || ')'
+  );
+END;
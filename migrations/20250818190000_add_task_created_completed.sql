@@ -0,0 +1,62 @@
+-- Days a task was created and completed on, todo.txt and other formats keep them too
+ALTER TABLE tasks ADD COLUMN created TEXT;
+ALTER TABLE tasks ADD COLUMN completed TEXT;
+
+-- The undo log has to restore the new columns too
+DROP TRIGGER IF EXISTS tasks_undo_update;
+DROP TRIGGER IF EXISTS tasks_undo_delete;
+
+CREATE TRIGGER IF NOT EXISTS tasks_undo_update
+AFTER UPDATE OF
+  title, description, done, project_id, parent_id, sub_task_ids,
+  due, scheduled, priority, recurrence, position, deleted_at, created, completed
+ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'UPDATE tasks SET'
+    || ' title = ' || quote(OLD.title)
+    || ', description = ' || quote(OLD.description)
+    || ', done = ' || quote(OLD.done)
+    || ', project_id = ' || quote(OLD.project_id)
+    || ', parent_id = ' || quote(OLD.parent_id)
+    || ', sub_task_ids = ' || quote(OLD.sub_task_ids)
+    || ', due = ' || quote(OLD.due)
+    || ', scheduled = ' || quote(OLD.scheduled)
+    || ', priority = ' || quote(OLD.priority)
+    || ', recurrence = ' || quote(OLD.recurrence)
+    || ', position = ' || quote(OLD.position)
+    || ', deleted_at = ' || quote(OLD.deleted_at)
+    || ', created = ' || quote(OLD.created)
+    || ', completed = ' || quote(OLD.completed)
+    || ' WHERE id = ' || OLD.id
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_undo_delete
+AFTER DELETE ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'INSERT INTO tasks (id, title, description, done, project_id, parent_id, sub_task_ids,'
+    || ' due, scheduled, priority, recurrence, position, folded, deleted_at, created,'
+    || ' completed) VALUES ('
+    || OLD.id
+    || ', ' || quote(OLD.title)
+    || ', ' || quote(OLD.description)
+    || ', ' || quote(OLD.done)
+    || ', ' || quote(OLD.project_id)
+    || ', ' || quote(OLD.parent_id)
+    || ', ' || quote(OLD.sub_task_ids)
+    || ', ' || quote(OLD.due)
+    || ', ' || quote(OLD.scheduled)
+    || ', ' || quote(OLD.priority)
+    || ', ' || quote(OLD.recurrence)
+    || ', ' || quote(OLD.position)
+    || ', ' || quote(OLD.folded)
+    || ', ' || quote(OLD.deleted_at)
+    || ', ' || quote(OLD.created)
+    || ', ' || quote(OLD.completed)
+    || ')'
+  );
+END;
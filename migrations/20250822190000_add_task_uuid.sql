@@ -0,0 +1,86 @@
+-- Random UUID of each task, which other task managers and calendars know it by
+ALTER TABLE tasks ADD COLUMN uuid TEXT;
+-- JSON object of the attributes other formats had that tasks have no column for
+ALTER TABLE tasks ADD COLUMN extra TEXT;
+
+UPDATE tasks SET uuid = lower(printf('%s-%s-4%s-%s%s-%s',
+  hex(randomblob(4)), hex(randomblob(2)), substr(hex(randomblob(2)), 2),
+  substr('89AB', abs(random()) % 4 + 1, 1), substr(hex(randomblob(2)), 2), hex(randomblob(6))
+));
+CREATE UNIQUE INDEX IF NOT EXISTS tasks_uuid ON tasks (uuid);
+
+-- Tasks added without a UUID get a new one, this update is left out of the undo log
+CREATE TRIGGER IF NOT EXISTS tasks_uuid_after_insert
+AFTER INSERT ON tasks
+FOR EACH ROW
+WHEN NEW.uuid IS NULL
+BEGIN
+  UPDATE tasks
+  SET uuid = lower(printf('%s-%s-4%s-%s%s-%s',
+      hex(randomblob(4)), hex(randomblob(2)), substr(hex(randomblob(2)), 2),
+      substr('89AB', abs(random()) % 4 + 1, 1), substr(hex(randomblob(2)), 2), hex(randomblob(6))
+    ))
+  WHERE id = NEW.id;
+END;
+
+-- The undo log has to restore the new columns too, the UUID never changes after the insert
+DROP TRIGGER IF EXISTS tasks_undo_update;
+DROP TRIGGER IF EXISTS tasks_undo_delete;
+
+CREATE TRIGGER IF NOT EXISTS tasks_undo_update
+AFTER UPDATE OF
+  title, description, done, project_id, parent_id, sub_task_ids,
+  due, scheduled, priority, recurrence, position, deleted_at, created, completed, extra
+ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'UPDATE tasks SET'
+    || ' title = ' || quote(OLD.title)
+    || ', description = ' || quote(OLD.description)
+    || ', done = ' || quote(OLD.done)
+    || ', project_id = ' || quote(OLD.project_id)
+    || ', parent_id = ' || quote(OLD.parent_id)
+    || ', sub_task_ids = ' || quote(OLD.sub_task_ids)
+    || ', due = ' || quote(OLD.due)
+    || ', scheduled = ' || quote(OLD.scheduled)
+    || ', priority = ' || quote(OLD.priority)
+    || ', recurrence = ' || quote(OLD.recurrence)
+    || ', position = ' || quote(OLD.position)
+    || ', deleted_at = ' || quote(OLD.deleted_at)
+    || ', created = ' || quote(OLD.created)
+    || ', completed = ' || quote(OLD.completed)
+    || ', extra = ' || quote(OLD.extra)
+    || ' WHERE id = ' || OLD.id
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_undo_delete
+AFTER DELETE ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT INTO undolog (sql) VALUES (
+    'INSERT INTO tasks (id, title, description, done, project_id, parent_id, sub_task_ids,'
+    || ' due, scheduled, priority, recurrence, position, folded, deleted_at, created,'
+    || ' completed, uuid, extra) VALUES ('
+    || OLD.id
+    || ', ' || quote(OLD.title)
+    || ', ' || quote(OLD.description)
+    || ', ' || quote(OLD.done)
+    || ', ' || quote(OLD.project_id)
+    || ', ' || quote(OLD.parent_id)
+    || ', ' || quote(OLD.sub_task_ids)
+    || ', ' || quote(OLD.due)
+    || ', ' || quote(OLD.scheduled)
+    || ', ' || quote(OLD.priority)
+    || ', ' || quote(OLD.recurrence)
+    || ', ' || quote(OLD.position)
+    || ', ' || quote(OLD.folded)
+    || ', ' || quote(OLD.deleted_at)
+    || ', ' || quote(OLD.created)
+    || ', ' || quote(OLD.completed)
+    || ', ' || quote(OLD.uuid)
+    || ', ' || quote(OLD.extra)
+    || ')'
+  );
+END;
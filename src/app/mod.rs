@@ -7,5 +7,6 @@ mod view;
 // -- flatten
 pub use model::App;
 pub use update::message::Message;
+pub use update::support::reorder_tasks;
 
 // endregion: --- Modules
@@ -1,4 +1,9 @@
-use crate::objects::{Project, Task};
+use crate::{
+    app::Message,
+    keymap::{Key, KeyContext, Keymap},
+    objects::{Project, Task, TrashItem},
+};
+use std::{collections::HashMap, time::Instant};
 
 use crossterm::event::EventStream;
 use ratatui::widgets::ListState;
@@ -14,11 +19,14 @@ pub struct App {
     pub popover: Popover,
     pub state: State,
     pub view_data: ViewData,
+    pub keymap: Keymap,
 }
 
 #[derive(Debug, Default)]
 pub struct Mode {
     pub app_mode: AppMode,
+    /// Keys of a sequence being typed, like the `z` in `za`
+    pub pending_keys: Vec<Key>,
     pub add_task_mode: AddTaskMode,
     pub add_project_mode: AddProjectMode,
 }
@@ -30,14 +38,67 @@ pub enum AppMode {
     AddTask,
     AddSubTask,
     AddSiblingTask,
+    EditTask,
+    MoveTask,
     FocusProject,
     AddProject,
+    EditProject,
+    Search,
+    Trash,
+    Confirm,
     Quit,
 }
 
+impl AppMode {
+    /// Key bindings in effect in this mode
+    pub fn key_context(&self) -> KeyContext {
+        match self {
+            AppMode::FocusTask => KeyContext::Task,
+            AppMode::FocusProject => KeyContext::Project,
+            AppMode::AddTask
+            | AppMode::AddSubTask
+            | AppMode::AddSiblingTask
+            | AppMode::EditTask => KeyContext::AddTask,
+            AppMode::MoveTask => KeyContext::MoveTask,
+            AppMode::AddProject | AppMode::EditProject => KeyContext::AddProject,
+            AppMode::Search => KeyContext::Search,
+            AppMode::Trash => KeyContext::Trash,
+            AppMode::Confirm => KeyContext::Confirm,
+            AppMode::Quit => KeyContext::Global,
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct ViewData {
     pub task_depth: Vec<usize>,
+    /// Number of hidden subtasks of each folded task
+    pub hidden_tasks: HashMap<i64, usize>,
+    /// Done and total subtasks of each task, including hidden ones
+    pub sub_task_progress: HashMap<i64, (usize, usize)>,
+    /// Done and total tasks of the current project
+    pub task_counts: (usize, usize),
+    pub show_task_detail: bool,
+    pub show_help: bool,
+    /// Message shown in the status bar until it times out
+    pub status: Option<StatusMessage>,
+    /// Set when another program drew on the terminal, so everything is redrawn
+    pub needs_clear: bool,
+}
+
+#[derive(Debug)]
+pub struct StatusMessage {
+    pub text: String,
+    pub shown_at: Instant,
+}
+
+impl StatusMessage {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            shown_at: Instant::now(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Default)]
@@ -57,6 +118,8 @@ pub enum AddProjectMode {
 pub struct Popover {
     pub add_task: AddTask,
     pub add_project: AddProject,
+    pub search: Search,
+    pub confirm: Confirm,
 }
 
 #[derive(Debug)]
@@ -70,14 +133,32 @@ pub struct AddProject {
     pub name: TextArea<'static>,
 }
 
+#[derive(Debug)]
+pub struct Search {
+    pub query: TextArea<'static>,
+}
+
+/// Question asked before a destructive action
+#[derive(Debug, Default)]
+pub struct Confirm {
+    pub question: String,
+    /// Sent once the question is answered with yes
+    pub on_confirm: Option<Message>,
+    /// Mode to go back to once answered
+    pub return_mode: AppMode,
+}
+
 #[derive(Debug)]
 pub struct Twodo {
     pub tasks: Vec<Task>,
     pub projects: Vec<Project>,
+    pub trash: Vec<TrashItem>,
 }
 
 #[derive(Debug, Default)]
 pub struct State {
     pub task_state: ListState,
     pub project_state: ListState,
+    pub move_target_state: ListState,
+    pub trash_state: ListState,
 }
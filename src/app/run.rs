@@ -1,23 +1,22 @@
 use crate::{
     app::{
-        model::{App, AppMode},
+        model::{App, AppMode, StatusMessage},
         update::{message::Message, support::get_twodo},
     },
-    Result,
+    config::config,
+    constants::STATUS_TIMEOUT,
+    keymap::Keymap,
+    Error, Result,
 };
 
 use ratatui::DefaultTerminal;
 use sqlx::SqlitePool;
 use std::time::Duration;
 
-use super::model::ViewData;
-
 impl App {
-    const FRAMES_PER_SECOND: f32 = 30.0;
-
     pub async fn new(db: SqlitePool) -> Result<Self> {
-        let (twodo, task_depth) = get_twodo(&db).await?;
-        let view_data = ViewData { task_depth };
+        let (twodo, view_data) = get_twodo(&db).await?;
+        let keymap = Keymap::new(&config().keys).map_err(Error::custom)?;
         Ok(Self {
             db,
             mode: Default::default(),
@@ -26,6 +25,7 @@ impl App {
             state: Default::default(),
             popover: Default::default(),
             view_data,
+            keymap,
         })
     }
 
@@ -33,15 +33,29 @@ impl App {
         self.state.task_state.select_first();
         self.state.project_state.select_first();
 
-        let period = Duration::from_secs_f32(1.0 / Self::FRAMES_PER_SECOND);
+        let period = Duration::from_secs_f32(1.0 / f32::from(config().tui.frames_per_second));
         let mut interval = tokio::time::interval(period);
 
         while self.mode.app_mode != AppMode::Quit {
+            self.view_data
+                .status
+                .take_if(|status| status.shown_at.elapsed() >= STATUS_TIMEOUT);
+            if std::mem::take(&mut self.view_data.needs_clear) {
+                terminal.clear()?;
+            }
             terminal.draw(|frame| frame.render_widget(&mut self, frame.area()))?;
 
             let mut action = self.handle_event(&mut interval).await;
             while action != Message::Noop {
-                action = self.update(action).await?;
+                action = match self.update(action).await {
+                    Ok(action) => action,
+                    // Recoverable errors are reported without leaving the TUI
+                    Err(err) if !err.is_fatal() => {
+                        self.view_data.status = Some(StatusMessage::new(err.message()));
+                        Message::Noop
+                    }
+                    Err(err) => return Err(err),
+                };
             }
         }
 
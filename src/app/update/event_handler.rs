@@ -1,10 +1,14 @@
-use crate::app::{
-    model::{AddProjectMode, AddTaskMode, App, AppMode},
-    update::message::Message,
+use crate::{
+    app::{
+        model::{AddProjectMode, AddTaskMode, App},
+        update::message::Message,
+    },
+    cli::ReorderDirection,
+    keymap::{Action, Key, KeyContext, Lookup},
 };
 
 use futures::{FutureExt, StreamExt};
-use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::event::{Event, KeyEvent, KeyEventKind};
 use tokio::time::Interval;
 
 impl App {
@@ -28,116 +32,201 @@ impl App {
     }
 
     fn on_key_event(&mut self, key: KeyEvent) -> Message {
-        match self.mode.app_mode {
-            AppMode::FocusTask => on_focus_task_key_event(key),
-            AppMode::FocusProject => on_focus_project_key_event(key),
-            AppMode::AddTask => self.on_add_task_key_event(key),
-            AppMode::AddSubTask => self.on_add_task_key_event(key),
-            AppMode::AddSiblingTask => self.on_add_task_key_event(key),
-            AppMode::AddProject => self.on_add_project_key_event(key),
-            AppMode::Quit => unreachable!(),
+        // Any key closes the help popup
+        if self.view_data.show_help {
+            return Message::ToggleHelp;
         }
-    }
 
-    fn on_add_project_key_event(&mut self, key: KeyEvent) -> Message {
-        match (key.modifiers, key.code) {
-            (_, KeyCode::Esc)
-            | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => {
-                Message::AddProjectAbort
-            }
-            (KeyModifiers::CONTROL, KeyCode::Char(' ')) => Message::AddProjectCommit,
+        let context = self.mode.app_mode.key_context();
+        self.mode.pending_keys.push(Key::from(key));
 
-            (_, KeyCode::Tab) => match self.mode.add_project_mode {
-                AddProjectMode::AddName => Message::FocusAddProjectName,
-            },
+        match self.keymap.lookup(context, &self.mode.pending_keys) {
+            Lookup::Action(context, action) => {
+                self.mode.pending_keys.clear();
+                self.on_action(context, action)
+            }
+            Lookup::Pending => Message::Noop,
+            // Keys that are not bound are typed into the text fields
+            Lookup::Unbound => {
+                let pending_keys = std::mem::take(&mut self.mode.pending_keys);
+                let mut message = Message::Noop;
+                for key in pending_keys {
+                    let key = KeyEvent::new(key.code, key.modifiers);
+                    message = self.on_text_input(context, key);
+                }
+                message
+            }
+        }
+    }
 
-            _ => {
+    fn on_text_input(&mut self, context: KeyContext, key: KeyEvent) -> Message {
+        match context {
+            KeyContext::AddTask => {
+                match self.mode.add_task_mode {
+                    AddTaskMode::AddTitle => self.popover.add_task.title.input(key),
+                    AddTaskMode::AddDescription => self.popover.add_task.description.input(key),
+                };
+                Message::Noop
+            }
+            KeyContext::AddProject => {
                 match self.mode.add_project_mode {
                     AddProjectMode::AddName => self.popover.add_project.name.input(key),
                 };
                 Message::Noop
             }
+            // Filter the task list as the query changes
+            KeyContext::Search if self.popover.search.query.input(key) => Message::ReloadTask,
+            _ => Message::Noop,
         }
     }
 
-    fn on_add_task_key_event(&mut self, key: KeyEvent) -> Message {
-        match (key.modifiers, key.code) {
-            (_, KeyCode::Esc)
-            | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => {
-                Message::AddTaskAbort
-            }
-            (KeyModifiers::CONTROL, KeyCode::Char(' ')) => Message::AddTaskCommit,
+    fn on_action(&self, context: KeyContext, action: Action) -> Message {
+        match context {
+            KeyContext::Global => on_global_action(action),
+            KeyContext::Task => on_task_action(action),
+            KeyContext::Project => on_project_action(action),
+            KeyContext::MoveTask => on_move_task_action(action),
+            KeyContext::Trash => on_trash_action(action),
+            KeyContext::AddTask => self.on_add_task_action(action),
+            KeyContext::AddProject => self.on_add_project_action(action),
+            KeyContext::Search => on_search_action(action),
+            KeyContext::Confirm => on_confirm_action(action),
+        }
+    }
+
+    fn on_add_project_action(&self, action: Action) -> Message {
+        match action {
+            Action::Cancel => Message::AddProjectAbort,
+            Action::Confirm => Message::AddProjectCommit,
+            Action::NextField => match self.mode.add_project_mode {
+                AddProjectMode::AddName => Message::FocusAddProjectName,
+            },
+            _ => Message::Noop,
+        }
+    }
 
-            (_, KeyCode::Tab) => match self.mode.add_task_mode {
+    fn on_add_task_action(&self, action: Action) -> Message {
+        match action {
+            Action::Cancel => Message::AddTaskAbort,
+            Action::Confirm => Message::AddTaskCommit,
+            Action::NextField => match self.mode.add_task_mode {
                 AddTaskMode::AddTitle => Message::FocusAddTaskDescription,
                 AddTaskMode::AddDescription => Message::FocusAddTaskTitle,
             },
-
-            _ => {
-                match self.mode.add_task_mode {
-                    AddTaskMode::AddTitle => self.popover.add_task.title.input(key),
-                    AddTaskMode::AddDescription => self.popover.add_task.description.input(key),
-                };
-                Message::Noop
-            }
+            Action::OpenEditor => Message::AddTaskInEditor,
+            _ => Message::Noop,
         }
     }
 }
 
-fn on_focus_task_key_event(key: KeyEvent) -> Message {
-    match (key.modifiers, key.code) {
+fn on_search_action(action: Action) -> Message {
+    match action {
+        Action::Cancel => Message::SearchAbort,
+        Action::Confirm => Message::SearchCommit,
+        _ => Message::Noop,
+    }
+}
+
+fn on_confirm_action(action: Action) -> Message {
+    match action {
+        Action::Cancel => Message::ConfirmAbort,
+        Action::Confirm => Message::ConfirmCommit,
+        _ => Message::Noop,
+    }
+}
+
+fn on_task_action(action: Action) -> Message {
+    match action {
         // Internal navigation
-        (_, KeyCode::Char('j')) => Message::SelectNextTask,
-        (_, KeyCode::Char('k')) => Message::SelectPrevTask,
-        (_, KeyCode::Char('g')) => Message::SelectFirstTask,
-        (_, KeyCode::Char('G')) => Message::SelectLastTask,
+        Action::SelectNext => Message::SelectNextTask,
+        Action::SelectPrev => Message::SelectPrevTask,
+        Action::SelectFirst => Message::SelectFirstTask,
+        Action::SelectLast => Message::SelectLastTask,
+        Action::Search => Message::SearchBegin,
+        Action::ToggleDetail => Message::ToggleTaskDetail,
 
         // External navigation
-        (_, KeyCode::Tab) => Message::FocusProject,
+        Action::FocusProject => Message::FocusProject,
 
         // Manage tasks
-        (_, KeyCode::Char('i')) => Message::AddTaskBegin,
-        (_, KeyCode::Char('s')) => Message::AddSubTaskBegin,
-        (_, KeyCode::Char('a')) => Message::AddSiblingTaskBegin,
-        (_, KeyCode::Char('x')) => Message::DeleteTask,
-        (_, KeyCode::Char('r')) => Message::ReloadTask,
-        (_, KeyCode::Char(' ')) => Message::ToggleTaskStatus,
-
-        // Other key handlers
-        _ => on_global_key_event(key),
+        Action::Add => Message::AddTaskBegin,
+        Action::AddSubTask => Message::AddSubTaskBegin,
+        Action::AddSiblingTask => Message::AddSiblingTaskBegin,
+        Action::Edit => Message::EditTaskBegin,
+        Action::OpenEditor => Message::EditTaskInEditor,
+        Action::Delete => Message::DeleteTask,
+        Action::Reload => Message::ReloadTask,
+        Action::ToggleDone => Message::ToggleTaskStatus,
+        Action::CyclePriority => Message::CycleTaskPriority,
+        Action::Move => Message::MoveTaskBegin,
+        Action::ReorderUp => Message::ReorderTask(ReorderDirection::Up),
+        Action::ReorderDown => Message::ReorderTask(ReorderDirection::Down),
+        Action::Indent => Message::ReorderTask(ReorderDirection::Indent),
+        Action::Outdent => Message::ReorderTask(ReorderDirection::Outdent),
+
+        // Folds
+        Action::ToggleFold => Message::ToggleFold,
+        Action::OpenFold => Message::OpenFold,
+        Action::CloseFold => Message::CloseFold,
+        Action::FoldAll => Message::FoldAll,
+        Action::UnfoldAll => Message::UnfoldAll,
+        _ => Message::Noop,
+    }
+}
+
+fn on_move_task_action(action: Action) -> Message {
+    match action {
+        Action::Cancel => Message::MoveTaskAbort,
+        Action::Confirm => Message::MoveTaskCommit,
+        Action::SelectNext => Message::SelectNextMoveTarget,
+        Action::SelectPrev => Message::SelectPrevMoveTarget,
+        _ => Message::Noop,
+    }
+}
+
+fn on_trash_action(action: Action) -> Message {
+    match action {
+        Action::Cancel => Message::TrashAbort,
+        Action::Restore => Message::RestoreTrashItem,
+        Action::SelectNext => Message::SelectNextTrashItem,
+        Action::SelectPrev => Message::SelectPrevTrashItem,
+        _ => Message::Noop,
     }
 }
 
-fn on_focus_project_key_event(key: KeyEvent) -> Message {
-    match (key.modifiers, key.code) {
+fn on_project_action(action: Action) -> Message {
+    match action {
         // Internal navigation
-        (_, KeyCode::Char('j')) => Message::SelectNextProject,
-        (_, KeyCode::Char('k')) => Message::SelectPrevProject,
-        (_, KeyCode::Char('g')) => Message::SelectFirstProject,
-        (_, KeyCode::Char('G')) => Message::SelectLastProject,
+        Action::SelectNext => Message::SelectNextProject,
+        Action::SelectPrev => Message::SelectPrevProject,
+        Action::SelectFirst => Message::SelectFirstProject,
+        Action::SelectLast => Message::SelectLastProject,
 
         // External navigation
-        (_, KeyCode::Tab) => Message::FocusTask,
+        Action::FocusTask => Message::FocusTask,
 
         // Manage projects
-        (_, KeyCode::Char('i')) | (_, KeyCode::Char('a')) => Message::AddProjectBegin,
-        (_, KeyCode::Char('x')) => Message::DeleteProject,
-        (_, KeyCode::Char('r')) => Message::ReloadProject,
-
-        // Other key handlers
-        _ => on_global_key_event(key),
+        Action::Add => Message::AddProjectBegin,
+        Action::Edit => Message::EditProjectBegin,
+        Action::Delete => Message::DeleteProject,
+        Action::Reload => Message::ReloadProject,
+        _ => Message::Noop,
     }
 }
 
-fn on_global_key_event(key: KeyEvent) -> Message {
-    match (key.modifiers, key.code) {
-        // Quit on Ctrl-C or ESC or q
-        (_, KeyCode::Esc | KeyCode::Char('q'))
-        | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => Message::Quit,
+fn on_global_action(action: Action) -> Message {
+    match action {
+        Action::Quit => Message::Quit,
+
+        // History
+        Action::Undo => Message::Undo,
+        Action::Redo => Message::Redo,
 
         // Navigation
-        (_, KeyCode::Char('1')) => Message::FocusProject,
-        (_, KeyCode::Char('2')) => Message::FocusTask,
+        Action::FocusProject => Message::FocusProject,
+        Action::FocusTask => Message::FocusTask,
+        Action::OpenTrash => Message::TrashBegin,
+        Action::Help => Message::ToggleHelp,
         _ => Message::Noop,
     }
 }
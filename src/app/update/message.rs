@@ -4,10 +4,16 @@ use crate::cli;
 pub enum Message {
     Noop,
     Quit,
+    Undo,
+    Redo,
+    ToggleHelp,
     // Task messages
     AddTaskBegin,
     AddSubTaskBegin,
     AddSiblingTaskBegin,
+    EditTaskBegin,
+    EditTaskInEditor,
+    AddTaskInEditor,
     AddTaskCommit,
     AddTaskAbort,
     FocusTask,
@@ -16,11 +22,28 @@ pub enum Message {
     TaskOp(cli::TaskOp),
     ReloadTask,
     DeleteTask,
+    DeleteTaskCommit(i64),
     ToggleTaskStatus,
+    CycleTaskPriority,
+    ToggleTaskDetail,
+    MoveTaskBegin,
+    MoveTaskCommit,
+    MoveTaskAbort,
+    SelectNextMoveTarget,
+    SelectPrevMoveTarget,
+    ReorderTask(cli::ReorderDirection),
+    ToggleFold,
+    OpenFold,
+    CloseFold,
+    FoldAll,
+    UnfoldAll,
     SelectNextTask,
     SelectPrevTask,
     SelectFirstTask,
     SelectLastTask,
+    SearchBegin,
+    SearchCommit,
+    SearchAbort,
 
     // Project messages
     FocusProject,
@@ -29,10 +52,25 @@ pub enum Message {
     SelectFirstProject,
     SelectLastProject,
     AddProjectBegin,
+    EditProjectBegin,
     AddProjectCommit,
     AddProjectAbort,
     FocusAddProjectName,
     ProjectOp(cli::ProjectOp),
     ReloadProject,
     DeleteProject,
+    DeleteProjectCommit(i64),
+
+    // Trash messages
+    TrashBegin,
+    TrashAbort,
+    SelectNextTrashItem,
+    SelectPrevTrashItem,
+    RestoreTrashItem,
+    TrashOp(cli::TrashOp),
+    ReloadTrash,
+
+    // Confirm messages
+    ConfirmCommit,
+    ConfirmAbort,
 }
@@ -1,17 +1,31 @@
 use crate::{
     app::{
-        model::{AddProjectMode, AddTaskMode, App, AppMode},
+        model::{AddProjectMode, AddTaskMode, App, AppMode, Confirm},
         update::message::Message,
     },
     cli::{
-        ProjectAddArg, ProjectDeleteArg, ProjectOp, TaskAddArg, TaskDeleteArg, TaskDoneArg,
-        TaskListArg, TaskOp,
+        ProjectAddArg, ProjectDeleteArg, ProjectEditArg, ProjectOp, ReorderDirection, TaskAddArg,
+        TaskDeleteArg, TaskDoneArg, TaskEditArg, TaskFoldArg, TaskListArg, TaskMoveArg, TaskOp,
+        TaskReorderArg, TrashOp, TrashRestoreArg,
+    },
+    config::config,
+    controller::editor::edit_task_text,
+    controller::delegater::{
+        count_project_tasks, count_sub_tasks, delegate_project_op, delegate_task_op,
+        delegate_trash_op, delete_project_question, delete_task_question, read_project,
+        read_task, read_trash, replay_step, StepKind,
     },
-    controller::delegater::{delegate_project_op, delegate_task_op, read_project, read_task},
     Error, Result,
 };
 
-use super::support::reorder_tasks;
+use ratatui::crossterm::{
+    event::EventStream,
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::stdout;
+
+use super::support::{fold_tasks, reorder_tasks, sub_task_progress, task_counts};
 
 impl App {
     fn quit(&mut self) -> Result<Message> {
@@ -23,14 +37,22 @@ impl App {
         match action {
             Message::Noop => unreachable!(),
             Message::Quit => self.quit(),
+            Message::Undo => self.replay_step(StepKind::Undo).await,
+            Message::Redo => self.replay_step(StepKind::Redo).await,
+            Message::ToggleHelp => {
+                return_noop(|| self.view_data.show_help = !self.view_data.show_help)
+            }
             // Task messages
             Message::ReloadTask => self.reload_task().await,
             Message::TaskOp(op) => delegate_task_op(&self.db, op).await,
             Message::AddTaskBegin => self.add_task_begin(AppMode::AddTask),
             Message::AddSubTaskBegin => self.add_task_begin(AppMode::AddSubTask),
             Message::AddSiblingTaskBegin => self.add_task_begin(AppMode::AddSiblingTask),
+            Message::EditTaskBegin => self.edit_task_begin(),
+            Message::EditTaskInEditor => self.edit_task_in_editor(),
+            Message::AddTaskInEditor => self.add_task_in_editor(),
             Message::AddTaskCommit => self.add_task_commit(),
-            Message::AddTaskAbort => return_noop(|| self.mode.app_mode = AppMode::FocusTask),
+            Message::AddTaskAbort => self.add_task_abort(),
             Message::FocusAddTaskTitle => {
                 return_noop(|| self.mode.add_task_mode = AddTaskMode::AddTitle)
             }
@@ -40,10 +62,33 @@ impl App {
             Message::SelectNextTask => return_noop(|| self.state.task_state.select_next()),
             Message::SelectPrevTask => return_noop(|| self.state.task_state.select_previous()),
             Message::FocusTask => return_noop(|| self.mode.app_mode = AppMode::FocusTask),
-            Message::DeleteTask => self.delete_task(),
+            Message::DeleteTask => self.delete_task().await,
+            Message::DeleteTaskCommit(id) => self.delete_task_commit(id),
             Message::ToggleTaskStatus => self.toggle_task_status(),
+            Message::CycleTaskPriority => self.cycle_task_priority(),
+            Message::ToggleTaskDetail => {
+                return_noop(|| self.view_data.show_task_detail = !self.view_data.show_task_detail)
+            }
+            Message::MoveTaskBegin => self.move_task_begin(),
+            Message::MoveTaskCommit => self.move_task_commit(),
+            Message::MoveTaskAbort => return_noop(|| self.mode.app_mode = AppMode::FocusTask),
+            Message::SelectNextMoveTarget => {
+                return_noop(|| self.state.move_target_state.select_next())
+            }
+            Message::SelectPrevMoveTarget => {
+                return_noop(|| self.state.move_target_state.select_previous())
+            }
+            Message::ReorderTask(direction) => self.reorder_task(direction),
+            Message::ToggleFold => self.fold_task(None),
+            Message::OpenFold => self.fold_task(Some(false)),
+            Message::CloseFold => self.fold_task(Some(true)),
+            Message::FoldAll => self.fold_all(true),
+            Message::UnfoldAll => self.fold_all(false),
             Message::SelectFirstTask => return_noop(|| self.state.task_state.select_first()),
             Message::SelectLastTask => return_noop(|| self.state.task_state.select_last()),
+            Message::SearchBegin => return_noop(|| self.mode.app_mode = AppMode::Search),
+            Message::SearchCommit => self.search_commit(),
+            Message::SearchAbort => self.search_abort(),
 
             // Project messages
             Message::SelectNextProject => self.select_next_project(),
@@ -55,12 +100,36 @@ impl App {
             Message::FocusAddProjectName => {
                 return_noop(|| self.mode.add_project_mode = AddProjectMode::AddName)
             }
+            Message::EditProjectBegin => self.edit_project_begin(),
             Message::AddProjectCommit => self.add_project_commit(),
-            Message::AddProjectAbort => return_noop(|| self.mode.app_mode = AppMode::FocusProject),
+            Message::AddProjectAbort => self.add_project_abort(),
             Message::ProjectOp(op) => delegate_project_op(&self.db, op).await,
             Message::ReloadProject => self.reload_project().await,
-            Message::DeleteProject => self.delete_project(),
+            Message::DeleteProject => self.delete_project().await,
+            Message::DeleteProjectCommit(id) => self.delete_project_commit(id),
+
+            // Trash messages
+            Message::TrashBegin => self.trash_begin().await,
+            Message::TrashAbort => return_noop(|| self.mode.app_mode = AppMode::FocusTask),
+            Message::SelectNextTrashItem => return_noop(|| self.state.trash_state.select_next()),
+            Message::SelectPrevTrashItem => {
+                return_noop(|| self.state.trash_state.select_previous())
+            }
+            Message::RestoreTrashItem => self.restore_trash_item(),
+            Message::TrashOp(op) => delegate_trash_op(&self.db, op).await,
+            Message::ReloadTrash => self.reload_trash().await,
+
+            // Confirm messages
+            Message::ConfirmCommit => self.confirm_end(true),
+            Message::ConfirmAbort => self.confirm_end(false),
+        }
+    }
+
+    async fn replay_step(&mut self, kind: StepKind) -> Result<Message> {
+        if replay_step(&self.db, kind).await? {
+            return Ok(Message::ReloadProject);
         }
+        Ok(Message::Noop)
     }
 
     fn select_first_project(&mut self) -> Result<Message> {
@@ -96,11 +165,98 @@ impl App {
         Ok(Message::FocusAddProjectName)
     }
 
+    fn edit_project_begin(&mut self) -> Result<Message> {
+        let project = self
+            .state
+            .project_state
+            .selected()
+            .and_then(|i| self.twodo.projects.get(i))
+            .ok_or(Error::MissingProjectId)?;
+
+        self.popover.add_project.fill(&project.name);
+        self.mode.app_mode = AppMode::EditProject;
+        Ok(Message::FocusAddProjectName)
+    }
+
+    fn add_project_abort(&mut self) -> Result<Message> {
+        if self.mode.app_mode == AppMode::EditProject {
+            self.popover.add_project.clear();
+        }
+        self.mode.app_mode = AppMode::FocusProject;
+        Ok(Message::Noop)
+    }
+
     fn add_task_begin(&mut self, app_mode: AppMode) -> Result<Message> {
         self.mode.app_mode = app_mode;
         Ok(Message::FocusAddTaskTitle)
     }
 
+    fn edit_task_begin(&mut self) -> Result<Message> {
+        let task = self
+            .state
+            .task_state
+            .selected()
+            .and_then(|i| self.twodo.tasks.get(i))
+            .ok_or(Error::MissingTaskId)?;
+
+        self.popover
+            .add_task
+            .fill(&task.title, task.description.as_deref());
+        self.add_task_begin(AppMode::EditTask)
+    }
+
+    fn edit_task_in_editor(&mut self) -> Result<Message> {
+        let (id, title, description) = self
+            .state
+            .task_state
+            .selected()
+            .and_then(|i| self.twodo.tasks.get(i))
+            .map(|t| (t.id, t.title.clone(), t.description.clone().unwrap_or_default()))
+            .ok_or(Error::MissingTaskId)?;
+
+        let (title, description) = self.run_editor(&title, &description)?;
+        Ok(Message::TaskOp(TaskOp::Edit(TaskEditArg {
+            id,
+            title: Some(title),
+            description: Some(description),
+            ..Default::default()
+        })))
+    }
+
+    /// Continue the task being added or edited in the editor, it is saved on commit
+    fn add_task_in_editor(&mut self) -> Result<Message> {
+        let title = self.popover.add_task.title.lines()[0].clone();
+        let description = self.popover.add_task.description.lines().join("\n");
+
+        let (title, description) = self.run_editor(&title, &description)?;
+        self.popover.add_task.fill(&title, Some(&description));
+        Ok(Message::Noop)
+    }
+
+    /// Hand the terminal over to the editor until it exits
+    fn run_editor(&mut self, title: &str, description: &str) -> Result<(String, String)> {
+        // A running event stream would read the keys meant for the editor
+        self.event_stream = EventStream::new();
+        disable_raw_mode()?;
+        execute!(stdout(), LeaveAlternateScreen)?;
+
+        let edited = edit_task_text(title, description);
+
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen)?;
+        self.view_data.needs_clear = true;
+        edited
+    }
+
+    fn add_task_abort(&mut self) -> Result<Message> {
+        // Keep drafts of new tasks around, but not edits of existing ones
+        if self.mode.app_mode == AppMode::EditTask {
+            self.popover.add_task.clear();
+        }
+        self.mode.app_mode = AppMode::FocusTask;
+        Ok(Message::Noop)
+    }
+
     fn toggle_task_status(&mut self) -> Result<Message> {
         let task = self
             .state
@@ -117,20 +273,151 @@ impl App {
         Ok(Message::TaskOp(task_op))
     }
 
-    fn delete_task(&mut self) -> Result<Message> {
+    fn cycle_task_priority(&mut self) -> Result<Message> {
+        let task = self
+            .state
+            .task_state
+            .selected()
+            .map(|i| &self.twodo.tasks[i])
+            .ok_or(Error::MissingTaskId)?;
+
+        Ok(Message::TaskOp(TaskOp::Edit(TaskEditArg {
+            id: task.id,
+            priority: Some(task.priority.next()),
+            ..Default::default()
+        })))
+    }
+
+    fn move_task_begin(&mut self) -> Result<Message> {
+        self.state.task_state.selected().ok_or(Error::MissingTaskId)?;
+
+        // Start from the project the task is in
+        let selected = self.state.project_state.selected();
+        self.state.move_target_state.select(selected);
+        self.mode.app_mode = AppMode::MoveTask;
+        Ok(Message::Noop)
+    }
+
+    fn move_task_commit(&mut self) -> Result<Message> {
+        self.mode.app_mode = AppMode::FocusTask;
+
         let id = self
             .state
             .task_state
             .selected()
             .map(|i| self.twodo.tasks[i].id)
             .ok_or(Error::MissingTaskId)?;
+        let project_id = self
+            .state
+            .move_target_state
+            .selected()
+            .and_then(|i| self.twodo.projects.get(i))
+            .map(|p| p.id)
+            .ok_or(Error::MissingProjectId)?;
+
+        Ok(Message::TaskOp(TaskOp::Move(TaskMoveArg {
+            id,
+            project_id: Some(project_id),
+            root: true,
+            ..Default::default()
+        })))
+    }
+
+    fn reorder_task(&mut self, direction: ReorderDirection) -> Result<Message> {
+        let id = self
+            .state
+            .task_state
+            .selected()
+            .map(|i| self.twodo.tasks[i].id)
+            .ok_or(Error::MissingTaskId)?;
+
+        Ok(Message::TaskOp(TaskOp::Reorder(TaskReorderArg { id, direction })))
+    }
+
+    /// Fold, unfold or toggle (`None`) the selected task
+    ///
+    /// On a task without subtasks this acts on its parent instead, like folds in vim.
+    fn fold_task(&mut self, fold: Option<bool>) -> Result<Message> {
+        let index = self.state.task_state.selected().ok_or(Error::MissingTaskId)?;
+        let task = &self.twodo.tasks[index];
+
+        let index = if self.view_data.sub_task_progress.contains_key(&task.id) {
+            index
+        } else if let Some(parent_index) = task
+            .parent_id
+            .and_then(|id| self.twodo.tasks.iter().position(|t| t.id == id))
+        {
+            parent_index
+        } else {
+            return Ok(Message::Noop);
+        };
+
+        let task = &self.twodo.tasks[index];
+        let fold = fold.unwrap_or(!task.folded);
+        if fold == task.folded {
+            return Ok(Message::Noop);
+        }
+        let id = task.id;
+        self.state.task_state.select(Some(index));
+
+        Ok(Message::TaskOp(TaskOp::Fold(TaskFoldArg {
+            ids: vec![id],
+            unfold: !fold,
+            ..Default::default()
+        })))
+    }
+
+    fn fold_all(&mut self, fold: bool) -> Result<Message> {
+        let project_id = self
+            .state
+            .project_state
+            .selected()
+            .and_then(|i| self.twodo.projects.get(i))
+            .map(|p| p.id)
+            .ok_or(Error::MissingProjectId)?;
+
+        // Keep the selection on a task that stays visible
+        if fold {
+            let root_index = self.state.task_state.selected().and_then(|i| {
+                let depths = self.view_data.task_depth.get(..=i)?;
+                depths.iter().rposition(|&d| d == 0)
+            });
+            self.state.task_state.select(root_index);
+        }
+
+        Ok(Message::TaskOp(TaskOp::Fold(TaskFoldArg {
+            project_id: Some(project_id),
+            unfold: !fold,
+            ..Default::default()
+        })))
+    }
+
+    async fn delete_task(&mut self) -> Result<Message> {
+        let (id, title) = self
+            .state
+            .task_state
+            .selected()
+            .and_then(|i| self.twodo.tasks.get(i))
+            .map(|t| (t.id, t.title.clone()))
+            .ok_or(Error::MissingTaskId)?;
 
+        // Only a delete that takes subtasks along is worth a question
+        let sub_tasks = count_sub_tasks(&self.db, id).await?;
+        if config().confirm && sub_tasks > 0 {
+            let question = delete_task_question(&title, sub_tasks);
+            return self.confirm_begin(question, Message::DeleteTaskCommit(id));
+        }
+        Ok(Message::DeleteTaskCommit(id))
+    }
+
+    fn delete_task_commit(&mut self, id: i64) -> Result<Message> {
         self.state.task_state.select_previous();
 
-        Ok(Message::TaskOp(TaskOp::Delete(TaskDeleteArg { id })))
+        Ok(Message::TaskOp(TaskOp::Delete(TaskDeleteArg { id, yes: true })))
     }
 
     fn add_project_commit(&mut self) -> Result<Message> {
+        let editing = self.mode.app_mode == AppMode::EditProject;
         self.mode.app_mode = AppMode::FocusProject;
         let name = self.popover.add_project.name.lines()[0].trim().to_string();
         if name.is_empty() {
@@ -138,10 +425,23 @@ impl App {
         }
 
         self.popover.add_project.clear();
+        if editing {
+            let id = self
+                .state
+                .project_state
+                .selected()
+                .map(|i| self.twodo.projects[i].id)
+                .ok_or(Error::MissingProjectId)?;
+            return Ok(Message::ProjectOp(ProjectOp::Edit(ProjectEditArg { id, name })));
+        }
         Ok(Message::ProjectOp(ProjectOp::Add(ProjectAddArg { name })))
     }
 
     fn add_task_commit(&mut self) -> Result<Message> {
+        if self.mode.app_mode == AppMode::EditTask {
+            return self.edit_task_commit();
+        }
+
         let parent_id = match self.mode.app_mode {
             AppMode::AddSubTask => self
                 .state
@@ -190,6 +490,51 @@ impl App {
             description,
             project_id,
             parent_id,
+            ..Default::default()
+        })))
+    }
+
+    fn search_commit(&mut self) -> Result<Message> {
+        self.mode.app_mode = AppMode::FocusTask;
+        self.state.task_state.select_first();
+        Ok(Message::Noop)
+    }
+
+    fn search_abort(&mut self) -> Result<Message> {
+        self.mode.app_mode = AppMode::FocusTask;
+        self.popover.search.clear();
+        Ok(Message::ReloadTask)
+    }
+
+    fn edit_task_commit(&mut self) -> Result<Message> {
+        self.mode.app_mode = AppMode::FocusTask;
+
+        let id = self
+            .state
+            .task_state
+            .selected()
+            .map(|i| self.twodo.tasks[i].id)
+            .ok_or(Error::MissingTaskId)?;
+        let title = self.popover.add_task.title.lines()[0].trim().to_string();
+        let description = self
+            .popover
+            .add_task
+            .description
+            .lines()
+            .join("\n")
+            .trim()
+            .to_string();
+        if title.is_empty() {
+            return Ok(Message::Noop);
+        }
+
+        self.popover.add_task.clear();
+        Ok(Message::TaskOp(TaskOp::Edit(TaskEditArg {
+            id,
+            title: Some(title),
+            // An empty description clears it
+            description: Some(description),
+            ..Default::default()
         })))
     }
 
@@ -201,43 +546,144 @@ impl App {
             .and_then(|i| self.twodo.projects.get(i))
             .map(|p| p.id);
 
-        if project_id.is_none() {
+        let Some(project_id) = project_id else {
             return Err(Error::MissingProjectId);
-        }
+        };
 
         let task_list_arg = TaskListArg {
-            project_id,
-            number: None,
+            project_id: Some(project_id),
+            search: Some(self.popover.search.query.lines()[0].clone()),
+            ..Default::default()
         };
 
+        let selected_id = self
+            .state
+            .task_state
+            .selected()
+            .and_then(|i| self.twodo.tasks.get(i))
+            .map(|t| t.id);
+
         let tasks = read_task(&self.db, task_list_arg).await?;
+        let sub_task_progress = sub_task_progress(&tasks);
+        let task_counts = task_counts(&self.db, project_id).await?;
         let (reordered_tasks, task_depth) = reorder_tasks(tasks);
+        let (visible_tasks, task_depth, hidden_tasks) = fold_tasks(reordered_tasks, task_depth);
 
-        self.twodo.tasks = reordered_tasks;
+        self.twodo.tasks = visible_tasks;
         self.view_data.task_depth = task_depth;
+        self.view_data.hidden_tasks = hidden_tasks;
+        self.view_data.sub_task_progress = sub_task_progress;
+        self.view_data.task_counts = task_counts;
+
+        // Keep the selection on the same task when it moved around
+        if let Some(index) = selected_id
+            .and_then(|id| self.twodo.tasks.iter().position(|t| t.id == id))
+        {
+            self.state.task_state.select(Some(index));
+        }
 
         Ok(Message::Noop)
     }
 
     async fn reload_project(&mut self) -> Result<Message> {
         self.twodo.projects = read_project(&self.db).await?;
+
+        // The selected project may be gone after an undo
+        if self
+            .state
+            .project_state
+            .selected()
+            .is_some_and(|i| i >= self.twodo.projects.len())
+        {
+            let last = self.twodo.projects.len().checked_sub(1);
+            self.state.project_state.select(last);
+        }
         Ok(Message::ReloadTask)
     }
 
-    fn delete_project(&mut self) -> Result<Message> {
-        let id = self
+    async fn delete_project(&mut self) -> Result<Message> {
+        let (id, name) = self
             .state
             .project_state
             .selected()
-            .map(|i| self.twodo.projects[i].id)
+            .and_then(|i| self.twodo.projects.get(i))
+            .map(|p| (p.id, p.name.clone()))
             .ok_or(Error::MissingProjectId)?;
 
+        if config().confirm {
+            let tasks = count_project_tasks(&self.db, id).await?;
+            let question = delete_project_question(&name, tasks);
+            return self.confirm_begin(question, Message::DeleteProjectCommit(id));
+        }
+        Ok(Message::DeleteProjectCommit(id))
+    }
+
+    fn delete_project_commit(&mut self, id: i64) -> Result<Message> {
         self.state.project_state.select_previous();
 
         Ok(Message::ProjectOp(ProjectOp::Delete(ProjectDeleteArg {
             id,
+            yes: true,
         })))
     }
+
+    /// Ask `question`, sending `on_confirm` once it is answered with yes
+    fn confirm_begin(&mut self, question: String, on_confirm: Message) -> Result<Message> {
+        let return_mode = std::mem::replace(&mut self.mode.app_mode, AppMode::Confirm);
+        self.popover.confirm = Confirm {
+            question,
+            on_confirm: Some(on_confirm),
+            return_mode,
+        };
+        Ok(Message::Noop)
+    }
+
+    fn confirm_end(&mut self, confirmed: bool) -> Result<Message> {
+        let confirm = std::mem::take(&mut self.popover.confirm);
+        self.mode.app_mode = confirm.return_mode;
+        match confirm.on_confirm {
+            Some(message) if confirmed => Ok(message),
+            _ => Ok(Message::Noop),
+        }
+    }
+
+    async fn trash_begin(&mut self) -> Result<Message> {
+        self.twodo.trash = read_trash(&self.db).await?;
+        self.state.trash_state.select_first();
+        self.mode.app_mode = AppMode::Trash;
+        Ok(Message::Noop)
+    }
+
+    fn restore_trash_item(&mut self) -> Result<Message> {
+        let Some(item) = self
+            .state
+            .trash_state
+            .selected()
+            .and_then(|i| self.twodo.trash.get(i))
+        else {
+            return Ok(Message::Noop);
+        };
+
+        Ok(Message::TrashOp(TrashOp::Restore(TrashRestoreArg {
+            id: item.id,
+            project: item.kind == "project",
+        })))
+    }
+
+    async fn reload_trash(&mut self) -> Result<Message> {
+        self.twodo.trash = read_trash(&self.db).await?;
+
+        if self
+            .state
+            .trash_state
+            .selected()
+            .is_some_and(|i| i >= self.twodo.trash.len())
+        {
+            let last = self.twodo.trash.len().checked_sub(1);
+            self.state.trash_state.select(last);
+        }
+        Ok(Message::ReloadProject)
+    }
 }
 
 fn return_noop<F: FnMut()>(mut f: F) -> Result<Message> {
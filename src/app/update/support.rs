@@ -1,27 +1,45 @@
-use std::collections::HashMap;
+use std::{cmp::Reverse, collections::HashMap};
 
 use crate::{
-    app::model::Twodo,
+    app::model::{Twodo, ViewData},
     cli::TaskListArg,
     constants::PROJECT_INBOX_ID,
-    controller::delegater::read_task,
+    controller::delegater::{read_task, read_trash},
     objects::{Project, Task},
     Result,
 };
 
-pub async fn get_twodo(db: &sqlx::Pool<sqlx::Sqlite>) -> Result<(Twodo, Vec<usize>)> {
+pub async fn get_twodo(db: &sqlx::Pool<sqlx::Sqlite>) -> Result<(Twodo, ViewData)> {
     let task_list_arg = TaskListArg {
         project_id: Some(PROJECT_INBOX_ID),
-        number: None,
+        ..Default::default()
     };
     let unordered_tasks = read_task(db, task_list_arg).await?;
+    let sub_task_progress = sub_task_progress(&unordered_tasks);
+    let task_counts = task_counts(db, PROJECT_INBOX_ID).await?;
     let (tasks, task_depth) = reorder_tasks(unordered_tasks);
+    let (tasks, task_depth, hidden_tasks) = fold_tasks(tasks, task_depth);
     let projects = get_projects(db).await?;
-    Ok((Twodo { tasks, projects }, task_depth))
+    let trash = read_trash(db).await?;
+    let view_data = ViewData {
+        task_depth,
+        hidden_tasks,
+        sub_task_progress,
+        task_counts,
+        ..Default::default()
+    };
+    Ok((
+        Twodo {
+            tasks,
+            projects,
+            trash,
+        },
+        view_data,
+    ))
 }
 
 async fn get_projects(db: &sqlx::Pool<sqlx::Sqlite>) -> Result<Vec<Project>> {
-    sqlx::query_as::<_, Project>("SELECT * FROM projects")
+    sqlx::query_as::<_, Project>("SELECT * FROM projects WHERE deleted_at IS NULL")
         .fetch_all(db)
         .await
         .map_err(Into::into)
@@ -35,18 +53,34 @@ pub fn reorder_tasks(tasks: Vec<Task>) -> (Vec<Task>, Vec<usize>) {
 
     for (index, task) in tasks.iter().enumerate() {
         task_id_to_index.insert(task.id, index);
+    }
+
+    // Tasks whose parent is filtered out are shown as root tasks
+    for task in tasks.iter() {
+        let parent_id = task
+            .parent_id
+            .filter(|parent_id| task_id_to_index.contains_key(parent_id));
         parent_to_children
-            .entry(task.parent_id)
+            .entry(parent_id)
             .or_default()
             .push(task.id);
     }
 
+    // Siblings are ordered by descending priority, then by their manual position.
+    // They are pushed onto the stack in reverse so that the first sibling is popped first.
+    for children in parent_to_children.values_mut() {
+        children.sort_by_key(|id| {
+            let task = &tasks[task_id_to_index[id]];
+            (Reverse(task.priority), task.position, task.id)
+        });
+    }
+
     let mut reordered_task_ids = Vec::new();
     let mut stack = Vec::new();
 
     // Start traversal from root tasks (parent_id == None)
     if let Some(root_ids) = parent_to_children.get(&None) {
-        for &root_id in root_ids.iter() {
+        for &root_id in root_ids.iter().rev() {
             stack.push((root_id, 0));
         }
     }
@@ -56,7 +90,7 @@ pub fn reorder_tasks(tasks: Vec<Task>) -> (Vec<Task>, Vec<usize>) {
         reordered_task_ids.push(task_id);
         depths.push(depth);
         if let Some(children) = parent_to_children.get(&Some(task_id)) {
-            for &child_id in children.iter() {
+            for &child_id in children.iter().rev() {
                 stack.push((child_id, depth + 1));
             }
         }
@@ -69,6 +103,63 @@ pub fn reorder_tasks(tasks: Vec<Task>) -> (Vec<Task>, Vec<usize>) {
     (reordered_tasks, depths)
 }
 
+/// Number of done and total subtasks of every task with subtasks
+pub fn sub_task_progress(tasks: &[Task]) -> HashMap<i64, (usize, usize)> {
+    let mut progress: HashMap<i64, (usize, usize)> = HashMap::new();
+    for task in tasks.iter() {
+        if let Some(parent_id) = task.parent_id {
+            let (done, total) = progress.entry(parent_id).or_default();
+            *done += task.done as usize;
+            *total += 1;
+        }
+    }
+    progress
+}
+
+/// Number of done and total tasks of a project, whatever the search filters out
+pub async fn task_counts(db: &sqlx::Pool<sqlx::Sqlite>, project_id: i64) -> Result<(usize, usize)> {
+    let (done, total): (i64, i64) = sqlx::query_as(
+        "SELECT COALESCE(SUM(done), 0), COUNT(*)
+        FROM tasks
+        WHERE project_id = ?1 AND deleted_at IS NULL",
+    )
+    .bind(project_id)
+    .fetch_one(db)
+    .await?;
+    Ok((done as usize, total as usize))
+}
+
+/// Drop the subtasks of folded tasks from tasks ordered by `reorder_tasks`
+///
+/// Also returns the number of hidden tasks under each folded task.
+pub fn fold_tasks(
+    tasks: Vec<Task>,
+    depths: Vec<usize>,
+) -> (Vec<Task>, Vec<usize>, HashMap<i64, usize>) {
+    let mut visible_tasks = Vec::new();
+    let mut visible_depths = Vec::new();
+    let mut hidden_tasks = HashMap::new();
+    // Folded task and its depth, while walking its subtasks
+    let mut folded: Option<(i64, usize)> = None;
+
+    for (task, depth) in tasks.into_iter().zip(depths) {
+        if let Some((folded_id, folded_depth)) = folded {
+            if depth > folded_depth {
+                *hidden_tasks.entry(folded_id).or_insert(0) += 1;
+                continue;
+            }
+            folded = None;
+        }
+        if task.folded {
+            folded = Some((task.id, depth));
+        }
+        visible_tasks.push(task);
+        visible_depths.push(depth);
+    }
+
+    (visible_tasks, visible_depths, hidden_tasks)
+}
+
 // region:    --- Tests
 
 #[cfg(test)]
@@ -78,6 +169,7 @@ mod tests {
     use std::collections::HashSet;
 
     use super::*;
+    use crate::objects::Priority;
 
     #[tokio::test]
     async fn test_reorder_tasks() -> Result<()> {
@@ -104,6 +196,7 @@ mod tests {
                 project_id: 1,
                 parent_id,
                 sub_task_ids: sqlx::types::Json(Vec::new()),
+                ..Default::default()
             })
             .collect::<Vec<_>>();
 
@@ -116,13 +209,13 @@ mod tests {
             (Some(1), 2),
             (Some(2), 3),
             (Some(3), 4),
-            (Some(2), 7),
-            (Some(7), 9),
-            (Some(7), 8),
             (Some(2), 5),
             (Some(5), 6),
+            (Some(2), 7),
+            (Some(7), 8),
+            (Some(7), 9),
         ];
-        let expected_depth = [0, 1, 2, 3, 2, 3, 3, 2, 3];
+        let expected_depth = [0, 1, 2, 3, 2, 3, 2, 3, 3];
         let mut visited_task_ids = HashSet::new();
         for (parent_id, task_id) in expected {
             assert!(visited_task_ids.insert(task_id));
@@ -138,6 +231,163 @@ mod tests {
         assert_eq!(expected_depth, actual_depth.as_slice());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_reorder_tasks_by_priority() -> Result<()> {
+        // -- Setup & Fixtures
+        let parent_id_task_id_priority = [
+            (None, 1, Priority::None),
+            (None, 2, Priority::High),
+            (Some(1), 3, Priority::Low),
+            (Some(1), 4, Priority::Urgent),
+            (Some(1), 5, Priority::Low),
+            (Some(4), 6, Priority::None),
+        ];
+
+        let original_tasks = parent_id_task_id_priority
+            .into_iter()
+            .map(|(parent_id, id, priority)| Task {
+                id,
+                title: "test reorder by priority".to_string(),
+                description: None,
+                done: false,
+                project_id: 1,
+                parent_id,
+                sub_task_ids: sqlx::types::Json(Vec::new()),
+                priority,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        // -- Exec
+        let (reordered_tasks, actual_depth) = reorder_tasks(original_tasks);
+
+        // -- Check
+        let expected = [2, 1, 4, 6, 3, 5];
+        let expected_depth = [0, 0, 1, 2, 1, 1];
+        let actual = reordered_tasks.into_iter().map(|t| t.id).collect::<Vec<_>>();
+        assert_eq!(expected, actual.as_slice());
+        assert_eq!(expected_depth, actual_depth.as_slice());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reorder_tasks_by_position() -> Result<()> {
+        // -- Setup & Fixtures
+        let parent_id_task_id_position = [
+            (None, 1, 3),
+            (None, 2, 1),
+            (Some(1), 3, 9),
+            (Some(1), 4, 2),
+            (None, 5, 2),
+        ];
+
+        let original_tasks = parent_id_task_id_position
+            .into_iter()
+            .map(|(parent_id, id, position)| Task {
+                id,
+                title: "test reorder by position".to_string(),
+                parent_id,
+                position,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        // -- Exec
+        let (reordered_tasks, actual_depth) = reorder_tasks(original_tasks);
+
+        // -- Check
+        let actual = reordered_tasks.into_iter().map(|t| t.id).collect::<Vec<_>>();
+        assert_eq!([2, 5, 1, 4, 3], actual.as_slice());
+        assert_eq!([0, 0, 0, 1, 1], actual_depth.as_slice());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fold_tasks() -> Result<()> {
+        // -- Setup & Fixtures
+        // 1 -> 2 (folded) -> 3 -> 4, 2 -> 5, and 6 on its own
+        let parent_id_task_id = [
+            (None, 1),
+            (Some(1), 2),
+            (Some(2), 3),
+            (Some(3), 4),
+            (Some(2), 5),
+            (None, 6),
+        ];
+        let original_tasks = parent_id_task_id
+            .into_iter()
+            .map(|(parent_id, id)| Task {
+                id,
+                title: "test fold".to_string(),
+                parent_id,
+                folded: id == 2,
+                done: id == 5,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let progress = sub_task_progress(&original_tasks);
+        let (tasks, depths) = reorder_tasks(original_tasks);
+
+        // -- Exec
+        let (tasks, depths, hidden_tasks) = fold_tasks(tasks, depths);
+
+        // -- Check
+        let actual = tasks.into_iter().map(|t| t.id).collect::<Vec<_>>();
+        assert_eq!([1, 2, 6], actual.as_slice());
+        assert_eq!([0, 1, 0], depths.as_slice());
+        assert_eq!(hidden_tasks, HashMap::from([(2, 3)]));
+        assert_eq!(progress[&2], (1, 2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reorder_tasks_with_missing_parent() -> Result<()> {
+        // -- Setup & Fixtures
+        let parent_id_task_id = [(None, 1), (Some(2), 3), (Some(3), 4)];
+
+        let original_tasks = parent_id_task_id
+            .into_iter()
+            .map(|(parent_id, id)| Task {
+                id,
+                title: "test reorder with missing parent".to_string(),
+                parent_id,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        // -- Exec
+        let (reordered_tasks, actual_depth) = reorder_tasks(original_tasks);
+
+        // -- Check
+        let actual = reordered_tasks.into_iter().map(|t| t.id).collect::<Vec<_>>();
+        assert_eq!([1, 3, 4], actual.as_slice());
+        assert_eq!([0, 0, 1], actual_depth.as_slice());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_task_counts() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = sqlx::sqlite::SqlitePool::connect("sqlite::memory:").await?;
+        sqlx::migrate!("./migrations").run(&db).await?;
+        sqlx::query(
+            "INSERT INTO tasks (title, done, deleted_at) VALUES
+                ('open', false, NULL),
+                ('done', true, NULL),
+                ('trashed', true, datetime('now'))",
+        )
+        .execute(&db)
+        .await?;
+
+        // -- Exec
+        let counts = task_counts(&db, PROJECT_INBOX_ID).await?;
+
+        // -- Check
+        assert_eq!(counts, (1, 2));
+        assert_eq!(task_counts(&db, 42).await?, (0, 0));
+        Ok(())
+    }
 }
 
 // endregion: --- Tests
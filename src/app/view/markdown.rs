@@ -0,0 +1,133 @@
+use ratatui::{
+    style::{Style, Stylize},
+    text::{Line, Span},
+};
+
+/// Render Markdown-ish text into styled lines
+///
+/// Supports headings, bullet and numbered lists, block quotes, fenced code blocks
+/// and inline `code` and **bold** spans. Anything else is shown as is.
+pub(super) fn markdown_lines(text: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(Line::from(Span::raw(format!("  {line}")).yellow()));
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        let line = if let Some((level, heading)) = heading(trimmed) {
+            let style = match level {
+                1 => Style::new().bold().underlined(),
+                _ => Style::new().bold(),
+            };
+            Line::from(inline_spans(heading)).style(style)
+        } else if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| trimmed.strip_prefix(bullet))
+        {
+            let mut spans = vec![Span::raw(format!("{indent}• "))];
+            spans.extend(inline_spans(item));
+            Line::from(spans)
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let mut spans = vec![Span::raw(format!("{indent}│ ")).dim()];
+            spans.extend(inline_spans(quote.trim_start()));
+            Line::from(spans).italic()
+        } else {
+            Line::from(inline_spans(line))
+        };
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let heading = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, heading))
+}
+
+/// Split a line into plain, `code` and **bold** spans
+fn inline_spans(text: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let code = rest.find('`');
+        let bold = rest.find("**");
+        let (start, marker) = match (code, bold) {
+            (Some(c), Some(b)) if b < c => (b, "**"),
+            (Some(c), _) => (c, "`"),
+            (None, Some(b)) => (b, "**"),
+            (None, None) => break,
+        };
+
+        let after = &rest[start + marker.len()..];
+        let Some(end) = after.find(marker) else {
+            break;
+        };
+
+        if start > 0 {
+            spans.push(Span::raw(rest[..start].to_string()));
+        }
+        let inner = after[..end].to_string();
+        spans.push(match marker {
+            "`" => Span::raw(inner).yellow().on_dark_gray(),
+            _ => Span::raw(inner).bold(),
+        });
+        rest = &after[end + marker.len()..];
+    }
+
+    if !rest.is_empty() {
+        spans.push(Span::raw(rest.to_string()));
+    }
+    spans
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_markdown_lines() {
+        let text = "# Plan\nSome `code` and **bold** text\n- first\n  * nested\n```\n# not a heading\n```\n> quoted";
+
+        let lines = markdown_lines(text);
+
+        let actual = lines.iter().map(plain).collect::<Vec<_>>();
+        let expected = [
+            "Plan",
+            "Some code and bold text",
+            "• first",
+            "  • nested",
+            "  # not a heading",
+            "│ quoted",
+        ];
+        assert_eq!(expected, actual.as_slice());
+        assert_eq!(lines[1].spans[1].content, "code");
+        assert_eq!(lines[1].spans[3].content, "bold");
+    }
+
+    #[test]
+    fn test_unterminated_inline_markers() {
+        let lines = markdown_lines("a `b and **c");
+
+        assert_eq!(plain(&lines[0]), "a `b and **c");
+    }
+}
+
+// endregion: --- Tests
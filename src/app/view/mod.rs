@@ -1,6 +1,7 @@
 // region:    --- Modules
 mod widgets;
 mod render;
+mod markdown;
 
 pub(super) mod support;
 
@@ -1,4 +1,4 @@
-use crate::app::model::App;
+use crate::{app::model::App, config::config};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::Buffer,
@@ -10,14 +10,34 @@ impl Widget for &mut App {
     where
         Self: Sized,
     {
+        let [area, footer_layout] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        let project_pane = config().tui.project_pane;
         let [project_layout, task_layout] = Layout::new(
             Direction::Horizontal,
-            [Constraint::Percentage(20), Constraint::Percentage(80)],
+            [
+                Constraint::Percentage(project_pane),
+                Constraint::Percentage(100 - project_pane),
+            ],
         )
         .areas(area);
 
-        self.render_tasks(task_layout, buf);
+        if self.view_data.show_task_detail {
+            let [task_layout, detail_layout] = Layout::new(
+                Direction::Horizontal,
+                [Constraint::Percentage(60), Constraint::Percentage(40)],
+            )
+            .areas(task_layout);
+            self.render_tasks(task_layout, buf);
+            self.render_task_detail(detail_layout, buf);
+        } else {
+            self.render_tasks(task_layout, buf);
+        }
         self.render_projects(project_layout, buf);
         self.render_popup(area, buf);
+        self.render_status_bar(footer_layout, buf);
+        if self.view_data.show_help {
+            self.render_help(area, buf);
+        }
     }
 }
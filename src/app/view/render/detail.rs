@@ -0,0 +1,86 @@
+use crate::{
+    app::{model::App, view::markdown::markdown_lines},
+    config::config,
+};
+use ratatui::{
+    layout::Rect,
+    prelude::Buffer,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{block::Position, Block, BorderType, Borders, Paragraph, Widget, Wrap},
+};
+
+impl App {
+    pub(super) fn render_task_detail(&mut self, frame: Rect, buf: &mut Buffer) {
+        let detail_block = Block::new()
+            .title(Line::from(" Details ").centered().style(Style::new().bold()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title_position(Position::Top);
+
+        let Some(task) = self
+            .state
+            .task_state
+            .selected()
+            .and_then(|i| self.twodo.tasks.get(i))
+        else {
+            Paragraph::new(Line::from("No task selected").dim().centered())
+                .block(detail_block)
+                .render(frame, buf);
+            return;
+        };
+
+        let field = |name: &str, value: String| {
+            Line::from(vec![Span::raw(format!("{name:<10}")).dim(), Span::raw(value)])
+        };
+
+        let mut lines = vec![Line::from(task.title.clone()).bold(), Line::default()];
+        lines.push(field("id", task.id.to_string()));
+
+        let project = self
+            .twodo
+            .projects
+            .iter()
+            .find(|p| p.id == task.project_id)
+            .map_or_else(|| task.project_id.to_string(), |p| p.name.clone());
+        lines.push(field("project", project));
+
+        if let Some(parent_id) = task.parent_id {
+            let parent = self
+                .twodo
+                .tasks
+                .iter()
+                .find(|t| t.id == parent_id)
+                .map_or_else(|| parent_id.to_string(), |t| t.title.clone());
+            lines.push(field("parent", parent));
+        }
+
+        if let Some((done, total)) = self.view_data.sub_task_progress.get(&task.id) {
+            lines.push(field("subtasks", format!("{done}/{total} done")));
+        }
+
+        lines.push(field("priority", format!("{:?}", task.priority).to_lowercase()));
+        if let Some(due) = task.due {
+            lines.push(field("due", config().format_date(due)));
+        }
+        if let Some(scheduled) = task.scheduled {
+            lines.push(field("scheduled", config().format_date(scheduled)));
+        }
+        if let Some(recurrence) = task.recurrence.as_ref() {
+            lines.push(field("repeats", recurrence.clone()));
+        }
+        if !task.tags.is_empty() {
+            lines.push(field("tags", task.tags.join(", ")));
+        }
+
+        if let Some(description) = task.description.as_deref().filter(|d| !d.is_empty()) {
+            lines.push(Line::default());
+            lines.extend(markdown_lines(description));
+        }
+
+        Paragraph::new(lines)
+            .block(detail_block)
+            .wrap(Wrap { trim: false })
+            .render(frame, buf);
+    }
+}
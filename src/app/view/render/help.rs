@@ -0,0 +1,86 @@
+use crate::{
+    app::{model::App, view::support::centered_area},
+    keymap::{Action, KeyContext},
+};
+use ratatui::{
+    layout::Rect,
+    prelude::Buffer,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+
+impl App {
+    /// Bindings of the current mode, along with the ones it falls back to
+    pub(super) fn render_help(&mut self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_area(area, 50, 80);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title_top(Line::from(" Keys ").centered().bold())
+            .title_bottom(Line::from(" any key to close ").centered().dim());
+
+        let mut lines = Vec::new();
+        for context in self.mode.app_mode.key_context().chain() {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(context.to_string()).bold());
+            for action in context.actions() {
+                let keys = self.keymap.keys_for(*context, *action);
+                if keys.is_empty() {
+                    continue;
+                }
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {:<16}", keys.join(" "))).yellow(),
+                    Span::raw(action.description()),
+                ]));
+            }
+        }
+
+        Widget::render(Clear, popup_area, buf);
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+
+    /// One line of the main keys of the current mode
+    pub(super) fn render_footer(&mut self, area: Rect, buf: &mut Buffer) {
+        let context = self.mode.app_mode.key_context();
+        let mut spans = Vec::new();
+        for action in hint_actions(context) {
+            let Some(keys) = self.keymap.keys_for(context, *action).into_iter().next() else {
+                continue;
+            };
+            spans.push(Span::raw(format!(" {keys} ")).reversed());
+            spans.push(Span::raw(format!(" {}  ", action.description())).dim());
+        }
+
+        Line::from(spans).render(area, buf);
+    }
+}
+
+/// Actions worth a hint in the footer, the help popup has the rest
+fn hint_actions(context: KeyContext) -> &'static [Action] {
+    use Action::*;
+    match context {
+        KeyContext::Task => &[
+            Add,
+            AddSubTask,
+            AddSiblingTask,
+            Edit,
+            Delete,
+            ToggleDone,
+            Move,
+            Search,
+            FocusProject,
+            Help,
+            Quit,
+        ],
+        KeyContext::Project | KeyContext::Global => {
+            &[Add, Edit, Delete, FocusTask, OpenTrash, Help, Quit]
+        }
+        KeyContext::MoveTask => &[Confirm, Cancel, SelectNext, SelectPrev],
+        KeyContext::Trash => &[Restore, Cancel, SelectNext, SelectPrev],
+        KeyContext::AddTask => &[Confirm, NextField, OpenEditor, Cancel],
+        KeyContext::AddProject => &[Confirm, NextField, Cancel],
+        KeyContext::Search | KeyContext::Confirm => &[Confirm, Cancel],
+    }
+}
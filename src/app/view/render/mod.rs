@@ -3,5 +3,8 @@ mod app;
 mod task;
 mod project;
 mod popup;
+mod detail;
+mod help;
+mod status;
 
 // endregion: --- Modules
@@ -1,14 +1,29 @@
-use crate::app::{
-    model::{AddTaskMode, App, AppMode},
-    view::support::{focus_textarea, unfocus_textarea},
+use crate::{
+    app::{
+        model::{AddTaskMode, App, AppMode},
+        view::support::{centered_area, focus_textarea, unfocus_textarea},
+    },
+    config::config,
+};
+use ratatui::{
+    layout::Rect,
+    prelude::Buffer,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, List, ListItem, StatefulWidget, Widget},
 };
-use ratatui::{layout::Rect, prelude::Buffer, widgets::Widget};
 
 impl App {
     pub(super) fn render_popup(&mut self, area: Rect, buf: &mut Buffer) {
         match self.mode.app_mode {
-            AppMode::AddTask | AppMode::AddSubTask | AppMode::AddSiblingTask => self.render_add_task(area, buf),
-            AppMode::AddProject => self.render_add_project(area, buf),
+            AppMode::AddTask
+            | AppMode::AddSubTask
+            | AppMode::AddSiblingTask
+            | AppMode::EditTask => self.render_add_task(area, buf),
+            AppMode::AddProject | AppMode::EditProject => self.render_add_project(area, buf),
+            AppMode::MoveTask => self.render_move_task(area, buf),
+            AppMode::Trash => self.render_trash(area, buf),
+            AppMode::Confirm => self.popover.confirm.render(area, buf),
             _ => {}
         }
     }
@@ -31,4 +46,51 @@ impl App {
         focus_textarea(&mut self.popover.add_project.name);
         self.popover.add_project.render(area, buf);
     }
+
+    pub(super) fn render_move_task(&mut self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_area(area, 40, 50);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title_top(Line::from(" Move to project ").centered().bold());
+        let items = self
+            .twodo
+            .projects
+            .iter()
+            .map(|p| ListItem::from(p.name.clone()))
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().fg(config().accent()))
+            .highlight_symbol("󰜴 ");
+
+        Widget::render(Clear, popup_area, buf);
+        StatefulWidget::render(list, popup_area, buf, &mut self.state.move_target_state);
+    }
+
+    pub(super) fn render_trash(&mut self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_area(area, 60, 60);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title_top(Line::from(" Trash ").centered().bold())
+            .title_bottom(Line::from(" Enter: restore ").centered().dim());
+        let items = self
+            .twodo
+            .trash
+            .iter()
+            .map(|item| {
+                ListItem::from(Line::from(vec![
+                    Span::raw(format!("{:<8}", item.kind)).dim(),
+                    Span::raw(item.name.clone()),
+                    Span::raw(format!("  {}", item.deleted_at)).dim(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().fg(config().accent()))
+            .highlight_symbol("󰜴 ");
+
+        Widget::render(Clear, popup_area, buf);
+        StatefulWidget::render(list, popup_area, buf, &mut self.state.trash_state);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::app::model::App;
+use crate::{app::model::App, config::config};
 use ratatui::{
     layout::Rect,
     prelude::Buffer,
@@ -30,7 +30,7 @@ impl App {
 
         let list = List::new(items)
             .block(project_block)
-            .highlight_style(Style::new().green())
+            .highlight_style(Style::new().fg(config().accent()))
             .highlight_symbol("󰜴 ");
 
         StatefulWidget::render(list, frame, buf, &mut self.state.project_state);
@@ -0,0 +1,30 @@
+use crate::app::model::App;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    prelude::Buffer,
+    style::Stylize,
+    text::Line,
+    widgets::Widget,
+};
+
+impl App {
+    /// Key hints, or the last error while it lasts, and the task counts on the right
+    pub(super) fn render_status_bar(&mut self, area: Rect, buf: &mut Buffer) {
+        let (done, total) = self.view_data.task_counts;
+        let counts = format!(" {} open  {done} done  {total} total ", total - done);
+        let [message_layout, counts_layout] = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(counts.len() as u16),
+        ])
+        .areas(area);
+
+        match &self.view_data.status {
+            Some(status) => Line::from(format!(" {}", status.text))
+                .red()
+                .bold()
+                .render(message_layout, buf),
+            None => self.render_footer(message_layout, buf),
+        }
+        Line::from(counts).dim().render(counts_layout, buf);
+    }
+}
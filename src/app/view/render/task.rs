@@ -1,20 +1,44 @@
-use crate::app::model::App;
+use crate::{
+    app::{
+        model::{App, AppMode},
+        view::support::{focus_textarea, unfocus_textarea},
+    },
+    config::config,
+    objects::Priority,
+};
+use chrono::Local;
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     prelude::Buffer,
-    style::{Style, Stylize},
-    text::Line,
-    widgets::{block::Position, Block, BorderType, Borders, List, ListItem, StatefulWidget},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{
+        block::Position, Block, BorderType, Borders, List, ListItem, StatefulWidget, Widget,
+    },
 };
 
 impl App {
-    pub(super) fn render_tasks(&mut self, frame: Rect, buf: &mut Buffer) {
+    pub(super) fn render_tasks(&mut self, mut frame: Rect, buf: &mut Buffer) {
+        let searching = self.mode.app_mode == AppMode::Search;
+        if searching || !self.popover.search.is_empty() {
+            let [task_area, search_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).areas(frame);
+            if searching {
+                focus_textarea(&mut self.popover.search.query);
+            } else {
+                unfocus_textarea(&mut self.popover.search.query);
+            }
+            self.popover.search.render(search_area, buf);
+            frame = task_area;
+        }
+
         let task_block = Block::new()
             .title(Line::from(" Tasks ").centered().style(Style::new().bold()))
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .title_position(Position::Top);
 
+        let today = Local::now().date_naive();
         let rows = self
             .twodo
             .tasks
@@ -23,15 +47,76 @@ impl App {
             .map(|(t, &d)| {
                 let done = if t.done { "󰄳 " } else { "󰄰 " };
                 let depth = "  ".repeat(d);
-                ListItem::new(format!("{} {} {}", depth, done, t.title))
+                let hidden = self.view_data.hidden_tasks.get(&t.id);
+                let fold = if hidden.is_some() {
+                    "▸"
+                } else if self.view_data.sub_task_progress.contains_key(&t.id) {
+                    "▾"
+                } else {
+                    " "
+                };
+                let mut spans = vec![Span::raw(format!("{}{} {} ", depth, fold, done))];
+                if let Some(priority) = priority_span(t.priority) {
+                    spans.push(priority);
+                }
+                spans.push(Span::raw(t.title.clone()));
+                if let Some(hidden) = hidden {
+                    spans.push(Span::raw(format!(" [+{hidden}]")).dim());
+                }
+                for tag in t.tags.iter() {
+                    spans.push(Span::raw(" "));
+                    spans.push(tag_chip(tag));
+                }
+                if let Some(due) = t.due {
+                    let due = Span::raw(format!("  󰃭 {}", config().format_date(due)));
+                    spans.push(if t.is_overdue(today) {
+                        due.fg(config().overdue()).bold()
+                    } else {
+                        due.dim()
+                    });
+                }
+                if t.recurrence.is_some() {
+                    spans.push(Span::raw(" 󰑖").dim());
+                }
+                ListItem::new(Line::from(spans))
             })
             .collect::<Vec<_>>();
 
         let table = List::new(rows)
             .block(task_block)
-            .highlight_style(Style::new().green())
+            .highlight_style(Style::new().fg(config().accent()))
             .highlight_symbol("󰜴 ");
 
         StatefulWidget::render(table, frame, buf, &mut self.state.task_state);
     }
 }
+
+fn priority_span(priority: Priority) -> Option<Span<'static>> {
+    let span = match priority {
+        Priority::None => return None,
+        Priority::Low => Span::raw("! ").blue(),
+        Priority::Medium => Span::raw("!! ").yellow(),
+        Priority::High => Span::raw("!!! ").light_red(),
+        Priority::Urgent => Span::raw("!!!! ").red().bold(),
+    };
+    Some(span)
+}
+
+const TAG_COLORS: [Color; 6] = [
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Yellow,
+    Color::Green,
+    Color::LightRed,
+];
+
+/// Tags keep the same colour across runs by deriving it from the name
+fn tag_chip(tag: &str) -> Span<'static> {
+    let hash = tag
+        .bytes()
+        .fold(0usize, |acc, b| acc.wrapping_mul(31).wrapping_add(b as usize));
+    Span::raw(format!(" {tag} "))
+        .fg(Color::Black)
+        .bg(TAG_COLORS[hash % TAG_COLORS.len()])
+}
@@ -45,4 +45,10 @@ impl AddProject {
     pub fn clear(&mut self) {
         *self = Self::default();
     }
+
+    /// Replace the contents with those of an existing project
+    pub fn fill(&mut self, name: &str) {
+        self.clear();
+        self.name.insert_str(name);
+    }
 }
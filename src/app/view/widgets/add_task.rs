@@ -55,4 +55,13 @@ impl AddTask {
     pub fn clear(&mut self) {
         *self = Self::default();
     }
+
+    /// Replace the contents with those of an existing task
+    pub fn fill(&mut self, title: &str, description: Option<&str>) {
+        self.clear();
+        self.title.insert_str(title);
+        if let Some(description) = description {
+            self.description.insert_str(description);
+        }
+    }
 }
@@ -0,0 +1,26 @@
+use crate::app::{model::Confirm, view::support::centered_area};
+
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    prelude::{Buffer, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget, Wrap},
+};
+
+impl Widget for &Confirm {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [popup_area] = Layout::vertical([Constraint::Length(5)])
+            .flex(Flex::Center)
+            .areas(centered_area(area, 50, 100));
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title_top(Line::from(" Confirm ").centered().bold());
+
+        Widget::render(Clear, popup_area, buf);
+        Paragraph::new(self.question.as_str())
+            .centered()
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
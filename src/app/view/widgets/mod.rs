@@ -1,5 +1,7 @@
 // region:    --- Modules
 mod add_task;
 mod add_project;
+mod search;
+mod confirm;
 
 // endregion: --- Modules
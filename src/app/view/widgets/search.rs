@@ -0,0 +1,43 @@
+use crate::app::{model::Search, view::support::focus_textarea};
+
+use ratatui::{
+    layout::Rect,
+    prelude::{Buffer, Stylize},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Clear, Widget},
+};
+use tui_textarea::TextArea;
+
+impl Widget for &Search {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Widget::render(Clear, area, buf);
+        Widget::render(&self.query, area, buf);
+    }
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        let mut query = TextArea::default();
+        query.set_cursor_style(Style::default());
+        query.set_cursor_line_style(Style::default());
+        query.set_block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .title_top(Line::from(" Search ").centered().bold()),
+        );
+        focus_textarea(&mut query);
+
+        Self { query }
+    }
+}
+
+impl Search {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.query.lines()[0].trim().is_empty()
+    }
+}
@@ -1,12 +1,28 @@
 #![deny(missing_docs)]
 
-use crate::cli::{ProjectOp, TaskOp};
+use std::path::PathBuf;
+
+use crate::cli::{
+    ConfigOp, ExportArg, ImportArg, OutputFormat, ProjectOp, TagOp, TaskOp, TrashOp, WorkspaceOp,
+};
 use clap::{Parser, Subcommand};
 
 /// Twodo CLI
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
+    /// Output format of list commands
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub format: OutputFormat,
+
+    /// Database file to use, overrides the workspace and `TWODO_DB`
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "workspace")]
+    pub db: Option<PathBuf>,
+
+    /// Workspace to use instead of the current one
+    #[arg(long, global = true, value_name = "NAME")]
+    pub workspace: Option<String>,
+
     /// Operation for twodo
     #[command(subcommand)]
     pub item: Option<Item>,
@@ -22,6 +38,37 @@ pub enum Item {
     /// Task operations
     #[command(subcommand)]
     Task(TaskOp),
+
+    /// Tag operations
+    #[command(subcommand)]
+    Tag(TagOp),
+
+    /// Trash operations
+    #[command(subcommand)]
+    Trash(TrashOp),
+
+    /// Workspace operations
+    #[command(subcommand)]
+    Workspace(WorkspaceOp),
+
+    /// Config operations
+    #[command(subcommand)]
+    Config(ConfigOp),
+
+    /// Print the key bindings of the TUI
+    Keys,
+
+    /// Import tasks from another task manager
+    Import(ImportArg),
+
+    /// Export tasks for another task manager
+    Export(ExportArg),
+
+    /// Undo the last change
+    Undo,
+
+    /// Redo the last undone change
+    Redo,
 }
 
 // region:    --- Tests
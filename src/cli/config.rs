@@ -0,0 +1,35 @@
+#![deny(missing_docs)]
+use clap::{Args, Subcommand};
+
+/// Config operations
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum ConfigOp {
+    /// Print the value of a config key, like `tui.project_pane`
+    Get(ConfigGetArg),
+
+    /// Set a config key in the config file
+    Set(ConfigSetArg),
+
+    /// Print the path of the config file
+    Path,
+
+    /// Open the config file in $VISUAL or $EDITOR
+    Edit,
+}
+
+/// Get arguments for config
+#[derive(Debug, PartialEq, Args)]
+pub struct ConfigGetArg {
+    /// Dotted key to get
+    pub key: String,
+}
+
+/// Set arguments for config
+#[derive(Debug, PartialEq, Args)]
+pub struct ConfigSetArg {
+    /// Dotted key to set
+    pub key: String,
+
+    /// Value in TOML syntax, bare words are taken as strings
+    pub value: String,
+}
@@ -0,0 +1,39 @@
+#![deny(missing_docs)]
+use clap::{Args, ValueEnum};
+use std::path::PathBuf;
+
+/// Formats of other task managers that tasks are imported from and exported to
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ExchangeFormat {
+    /// One task per line, see http://todotxt.org
+    Todotxt,
+    /// JSON of `task export`, see https://taskwarrior.org
+    Taskwarrior,
+    /// GitHub flavoured Markdown checklists, with subtasks nested in their parent
+    Markdown,
+    /// iCalendar to-dos, see RFC 5545
+    Ics,
+}
+
+/// Import arguments
+#[derive(Debug, PartialEq, Args)]
+pub struct ImportArg {
+    /// Format of the file
+    #[arg(value_enum)]
+    pub from: ExchangeFormat,
+
+    /// File to read the tasks from, `-` for stdin
+    pub file: PathBuf,
+}
+
+/// Export arguments
+#[derive(Debug, PartialEq, Args)]
+pub struct ExportArg {
+    /// Format to write the tasks to stdout in
+    #[arg(value_enum)]
+    pub to: ExchangeFormat,
+
+    /// Only export the tasks of this project
+    #[arg(short, long = "project")]
+    pub project_id: Option<i64>,
+}
@@ -0,0 +1,25 @@
+#![deny(missing_docs)]
+use clap::ValueEnum;
+
+/// Output format of list commands
+#[derive(Debug, Default, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// One line per item, meant for humans
+    #[default]
+    Plain,
+
+    /// Aligned columns with a header
+    Table,
+
+    /// A single JSON array
+    Json,
+
+    /// One JSON object per line
+    Ndjson,
+
+    /// Comma separated values with a header
+    Csv,
+
+    /// Tab separated values with a header
+    Tsv,
+}
@@ -1,11 +1,25 @@
 // region:    --- Modules
 mod cmd;
+mod config;
+mod exchange;
+mod format;
+mod parse;
 mod project;
+mod tag;
 mod task;
+mod trash;
+mod workspace;
 
 // -- Flatten
 pub use cmd::*;
+pub use config::*;
+pub use exchange::*;
+pub use format::*;
+pub use parse::*;
 pub use project::*;
+pub use tag::*;
 pub use task::*;
+pub use trash::*;
+pub use workspace::*;
 
 // endregion: --- Modules
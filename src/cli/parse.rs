@@ -0,0 +1,46 @@
+use crate::config::config;
+use chrono::{Days, Local, NaiveDate};
+
+/// Parse a date given on the command line
+///
+/// Accepts `YYYY-MM-DD`, the configured date format, `today`, `tomorrow`, `yesterday` or `+N` for
+/// N days from today.
+pub fn parse_date(arg: &str) -> Result<NaiveDate, String> {
+    let today = Local::now().date_naive();
+    match arg.trim().to_lowercase().as_str() {
+        "today" => Ok(today),
+        "tomorrow" => Ok(today + Days::new(1)),
+        "yesterday" => Ok(today - Days::new(1)),
+        offset if offset.starts_with('+') => offset[1..]
+            .parse::<u64>()
+            .map(|days| today + Days::new(days))
+            .map_err(|_| format!("invalid day offset '{arg}', expected +N")),
+        date => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(arg.trim(), &config().date_format))
+            .map_err(|_| format!("invalid date '{arg}', expected YYYY-MM-DD")),
+    }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date() {
+        let today = Local::now().date_naive();
+
+        assert_eq!(
+            parse_date("2025-07-20"),
+            Ok(NaiveDate::from_ymd_opt(2025, 7, 20).unwrap())
+        );
+        assert_eq!(parse_date("today"), Ok(today));
+        assert_eq!(parse_date("Tomorrow"), Ok(today + Days::new(1)));
+        assert_eq!(parse_date("+7"), Ok(today + Days::new(7)));
+        assert!(parse_date("20/07/2025").is_err());
+        assert!(parse_date("+x").is_err());
+    }
+}
+
+// endregion: --- Tests
@@ -40,4 +40,8 @@ pub struct ProjectEditArg {
 pub struct ProjectDeleteArg {
     /// Id of project to delete
     pub id: i64,
+
+    /// Delete without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
 }
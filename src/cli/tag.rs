@@ -0,0 +1,56 @@
+#![deny(missing_docs)]
+use clap::{Args, Subcommand};
+
+/// Tag operations
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum TagOp {
+    /// List all tags
+    List,
+
+    /// Add a tag
+    Add(TagAddArg),
+
+    /// Rename a tag
+    Rename(TagRenameArg),
+
+    /// Delete a tag
+    Delete(TagDeleteArg),
+
+    /// Merge a tag into another tag
+    Merge(TagMergeArg),
+}
+
+/// Add arguments for tag
+#[derive(Debug, PartialEq, Args)]
+pub struct TagAddArg {
+    /// Name of tag
+    pub name: String,
+}
+
+/// Rename arguments for tag
+#[derive(Debug, PartialEq, Args)]
+pub struct TagRenameArg {
+    /// Id of tag to rename
+    pub id: i64,
+
+    /// New name of tag
+    #[arg(short, long)]
+    pub name: String,
+}
+
+/// Delete arguments for tag
+#[derive(Debug, PartialEq, Args)]
+pub struct TagDeleteArg {
+    /// Id of tag to delete
+    pub id: i64,
+}
+
+/// Merge arguments for tag
+#[derive(Debug, PartialEq, Args)]
+pub struct TagMergeArg {
+    /// Id of tag to merge, it is deleted afterwards
+    pub from: i64,
+
+    /// Id of tag to merge into
+    pub into: i64,
+}
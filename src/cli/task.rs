@@ -1,5 +1,12 @@
 #![deny(missing_docs)]
-use clap::{Args, Subcommand};
+use crate::{
+    cli::parse_date,
+    config::config,
+    constants::PROJECT_INBOX_ID,
+    objects::{Priority, Recurrence},
+};
+use chrono::NaiveDate;
+use clap::{ArgGroup, Args, Subcommand, ValueEnum};
 
 /// Task operations
 #[derive(Subcommand, Debug, PartialEq)]
@@ -21,6 +28,18 @@ pub enum TaskOp {
 
     /// Delete a task
     Delete(TaskDeleteArg),
+
+    /// Move a task and its subtasks to another project or parent
+    Move(TaskMoveArg),
+
+    /// Reorder a task among its siblings or change its depth
+    Reorder(TaskReorderArg),
+
+    /// Hide or show the subtasks of tasks in the TUI
+    Fold(TaskFoldArg),
+
+    /// Search tasks by title and description
+    Search(TaskSearchArg),
 }
 
 /// List arguments for task
@@ -33,6 +52,30 @@ pub struct TaskListArg {
     /// Number of task to list
     #[arg(short)]
     pub number: Option<usize>,
+
+    /// List tasks due before this date
+    #[arg(long, value_parser = parse_date)]
+    pub due_before: Option<NaiveDate>,
+
+    /// List tasks scheduled before this date
+    #[arg(long, value_parser = parse_date)]
+    pub scheduled_before: Option<NaiveDate>,
+
+    /// List only tasks that are not done and past their due date
+    #[arg(long)]
+    pub overdue: bool,
+
+    /// List overdue and other tasks alike, even when the config lists only overdue ones
+    #[arg(long, conflicts_with = "overdue")]
+    pub no_overdue: bool,
+
+    /// List only tasks with this tag, can be repeated
+    #[arg(long = "tag")]
+    pub tags: Vec<String>,
+
+    /// List only tasks matching this search query
+    #[arg(short, long)]
+    pub search: Option<String>,
 }
 
 /// Add arguments for task
@@ -46,16 +89,52 @@ pub struct TaskAddArg {
     pub description: Option<String>,
 
     /// Project id for task
-    #[arg(short, long, default_value_t = 1)]
+    #[arg(short, long, default_value_t = config().default_project)]
     pub project_id: i64,
 
     /// Parent task id for task
     #[arg(long)]
     pub parent_id: Option<i64>,
+
+    /// Due date for task
+    #[arg(long, value_parser = parse_date)]
+    pub due: Option<NaiveDate>,
+
+    /// Scheduled date for task
+    #[arg(long, value_parser = parse_date)]
+    pub scheduled: Option<NaiveDate>,
+
+    /// Priority of task
+    #[arg(long, value_enum, default_value_t)]
+    pub priority: Priority,
+
+    /// Tag for task, can be repeated
+    #[arg(long = "tag")]
+    pub tags: Vec<String>,
+
+    /// Recurrence of task, e.g. daily, weekdays, "every 2 weeks" or an RRULE
+    #[arg(long)]
+    pub recur: Option<Recurrence>,
+}
+
+impl Default for TaskAddArg {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            description: None,
+            project_id: PROJECT_INBOX_ID,
+            parent_id: None,
+            due: None,
+            scheduled: None,
+            priority: Priority::None,
+            tags: Vec::new(),
+            recur: None,
+        }
+    }
 }
 
 /// Edit arguments for task
-#[derive(Debug, PartialEq, Args)]
+#[derive(Debug, Default, PartialEq, Args)]
 pub struct TaskEditArg {
     /// Id of task to edit
     pub id: i64,
@@ -67,6 +146,46 @@ pub struct TaskEditArg {
     /// Description of task
     #[arg(short, long)]
     pub description: Option<String>,
+
+    /// Edit the title and description in $EDITOR, the title on the first line
+    #[arg(long, conflicts_with_all = ["title", "description"])]
+    pub editor: bool,
+
+    /// Due date of task
+    #[arg(long, value_parser = parse_date, conflicts_with = "clear_due")]
+    pub due: Option<NaiveDate>,
+
+    /// Scheduled date of task
+    #[arg(long, value_parser = parse_date, conflicts_with = "clear_scheduled")]
+    pub scheduled: Option<NaiveDate>,
+
+    /// Priority of task
+    #[arg(long, value_enum)]
+    pub priority: Option<Priority>,
+
+    /// Recurrence of task, e.g. daily, weekdays, "every 2 weeks" or an RRULE
+    #[arg(long, conflicts_with = "clear_recur")]
+    pub recur: Option<Recurrence>,
+
+    /// Remove the due date of task
+    #[arg(long)]
+    pub clear_due: bool,
+
+    /// Remove the scheduled date of task
+    #[arg(long)]
+    pub clear_scheduled: bool,
+
+    /// Stop the task from recurring
+    #[arg(long)]
+    pub clear_recur: bool,
+
+    /// Tag to add to task, can be repeated
+    #[arg(long = "tag")]
+    pub tags: Vec<String>,
+
+    /// Tag to remove from task, can be repeated
+    #[arg(long = "untag")]
+    pub untags: Vec<String>,
 }
 
 /// Delete arguments for task
@@ -74,6 +193,86 @@ pub struct TaskEditArg {
 pub struct TaskDeleteArg {
     /// Id of task to delete
     pub id: i64,
+
+    /// Delete without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+/// Move arguments for task
+#[derive(Debug, Default, PartialEq, Args)]
+#[command(group(ArgGroup::new("target").required(true).multiple(true)))]
+pub struct TaskMoveArg {
+    /// Id of task to move
+    pub id: i64,
+
+    /// Project to move the task to, the task becomes a top level task unless a parent is given
+    #[arg(short, long = "project", group = "target")]
+    pub project_id: Option<i64>,
+
+    /// Task to move the task under
+    #[arg(long = "parent", group = "target", conflicts_with = "root")]
+    pub parent_id: Option<i64>,
+
+    /// Make the task a top level task
+    #[arg(long, group = "target")]
+    pub root: bool,
+}
+
+/// Reorder arguments for task
+#[derive(Debug, PartialEq, Args)]
+pub struct TaskReorderArg {
+    /// Id of task to reorder
+    pub id: i64,
+
+    /// Where to move the task
+    #[arg(value_enum)]
+    pub direction: ReorderDirection,
+}
+
+/// Direction to reorder a task in
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ReorderDirection {
+    /// Before the previous sibling
+    Up,
+    /// After the next sibling
+    Down,
+    /// Under the previous sibling, as its last subtask
+    Indent,
+    /// Next to its parent, right after it
+    Outdent,
+}
+
+/// Fold arguments for task
+#[derive(Debug, Default, PartialEq, Args)]
+#[command(group(ArgGroup::new("tasks").required(true).multiple(true)))]
+pub struct TaskFoldArg {
+    /// Ids of tasks to fold
+    #[arg(group = "tasks")]
+    pub ids: Vec<i64>,
+
+    /// Fold every task in project
+    #[arg(short, long, group = "tasks")]
+    pub project_id: Option<i64>,
+
+    /// Show the subtasks again
+    #[arg(long)]
+    pub unfold: bool,
+}
+
+/// Search arguments for task
+#[derive(Debug, PartialEq, Args)]
+pub struct TaskSearchArg {
+    /// Words to search for, matching word prefixes
+    pub query: String,
+
+    /// Search tasks in project
+    #[arg(short, long)]
+    pub project_id: Option<i64>,
+
+    /// Number of task to list
+    #[arg(short)]
+    pub number: Option<usize>,
 }
 
 /// Done arguments for task
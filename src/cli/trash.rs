@@ -0,0 +1,34 @@
+#![deny(missing_docs)]
+use clap::{Args, Subcommand};
+
+/// Trash operations
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum TrashOp {
+    /// List deleted tasks and projects
+    List,
+
+    /// Restore a deleted task or project
+    Restore(TrashRestoreArg),
+
+    /// Permanently delete what is in the trash
+    Purge(TrashPurgeArg),
+}
+
+/// Restore arguments for trash
+#[derive(Debug, PartialEq, Args)]
+pub struct TrashRestoreArg {
+    /// Id of task to restore, along with the subtasks deleted with it
+    pub id: i64,
+
+    /// Restore the project with this id instead, along with its tasks
+    #[arg(short, long)]
+    pub project: bool,
+}
+
+/// Purge arguments for trash
+#[derive(Debug, Default, PartialEq, Args)]
+pub struct TrashPurgeArg {
+    /// Only purge what was deleted more than this many days ago
+    #[arg(long, value_name = "DAYS")]
+    pub older_than: Option<u32>,
+}
@@ -0,0 +1,25 @@
+#![deny(missing_docs)]
+use clap::{Args, Subcommand};
+
+/// Workspace operations
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum WorkspaceOp {
+    /// List workspaces, marking the current one
+    List,
+
+    /// Create a workspace with its own database
+    Create(WorkspaceArg),
+
+    /// Make a workspace the one used when none is given
+    Switch(WorkspaceArg),
+
+    /// Remove a workspace along with its database
+    Remove(WorkspaceArg),
+}
+
+/// Workspace arguments
+#[derive(Debug, PartialEq, Args)]
+pub struct WorkspaceArg {
+    /// Name of the workspace
+    pub name: String,
+}
@@ -0,0 +1,338 @@
+use crate::{
+    cli::TaskListArg,
+    constants::PROJECT_INBOX_ID,
+    keymap::{Action, KeyContext, Keymap},
+    Error, Result,
+};
+use chrono::{
+    format::{Item, StrftimeItems},
+    NaiveDate,
+};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Settings read from `config.toml`, every field is optional in the file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Project new tasks go to when none is given
+    pub default_project: i64,
+    /// strftime format dates are shown in, and accepted in besides YYYY-MM-DD
+    pub date_format: String,
+    /// Ask before deleting tasks and projects
+    pub confirm: bool,
+    pub theme: ThemeConfig,
+    pub tui: TuiConfig,
+    pub list: ListConfig,
+    /// Key bindings replacing the defaults, by context then key sequence
+    pub keys: BTreeMap<KeyContext, BTreeMap<String, Action>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    /// Color of the selected item, a name like `green` or `#rrggbb`
+    pub accent: String,
+    /// Color of due dates that are past
+    pub overdue: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TuiConfig {
+    /// Width of the project pane in percent
+    pub project_pane: u16,
+    pub frames_per_second: u16,
+}
+
+/// Filters `twodo task list` applies unless given on the command line
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ListConfig {
+    pub number: Option<usize>,
+    pub overdue: bool,
+    pub tags: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            default_project: PROJECT_INBOX_ID,
+            date_format: "%Y-%m-%d".to_string(),
+            confirm: true,
+            theme: Default::default(),
+            tui: Default::default(),
+            list: Default::default(),
+            keys: Default::default(),
+        }
+    }
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            accent: "green".to_string(),
+            overdue: "red".to_string(),
+        }
+    }
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            project_pane: 20,
+            frames_per_second: 30,
+        }
+    }
+}
+
+/// Keys accepted by `twodo config get` and `twodo config set`
+pub const CONFIG_KEYS: &[&str] = &[
+    "default_project",
+    "date_format",
+    "confirm",
+    "theme.accent",
+    "theme.overdue",
+    "tui.project_pane",
+    "tui.frames_per_second",
+    "list.number",
+    "list.overdue",
+    "list.tags",
+];
+
+/// The loaded config, or the defaults when none was loaded
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+/// Load the config file into [`config`], a missing file leaves the defaults
+pub fn init() -> Result<()> {
+    let config = Config::load(&config_path()?)?;
+    // Only fails when already initialized, which keeps the first one
+    let _ = CONFIG.set(config);
+    Ok(())
+}
+
+/// `$XDG_CONFIG_HOME/twodo/config.toml` or `~/.config/twodo/config.toml`
+pub fn config_path() -> Result<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::home_dir().map(|h| h.join(".config")))
+        .ok_or_else(|| Error::custom("Failed to find the home directory, set XDG_CONFIG_HOME"))?;
+    Ok(config_home.join("twodo").join("config.toml"))
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content)
+                .map_err(|err| Error::custom(format!("Invalid config {}: {err}", path.display()))),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Parse and validate the content of a config file
+    pub fn parse(content: &str) -> core::result::Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|err| err.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> core::result::Result<(), String> {
+        if StrftimeItems::new(&self.date_format).any(|item| matches!(item, Item::Error)) {
+            return Err(format!(
+                "date_format {:?} is not a valid strftime format",
+                self.date_format
+            ));
+        }
+        for (key, color) in [
+            ("accent", &self.theme.accent),
+            ("overdue", &self.theme.overdue),
+        ] {
+            if Color::from_str(color).is_err() {
+                return Err(format!(
+                    "theme.{key} {color:?} is not a color name or #rrggbb"
+                ));
+            }
+        }
+        if !(10..=90).contains(&self.tui.project_pane) {
+            return Err(format!(
+                "tui.project_pane must be between 10 and 90, got {}",
+                self.tui.project_pane
+            ));
+        }
+        if !(1..=240).contains(&self.tui.frames_per_second) {
+            return Err(format!(
+                "tui.frames_per_second must be between 1 and 240, got {}",
+                self.tui.frames_per_second
+            ));
+        }
+        Keymap::new(&self.keys)?;
+        Ok(())
+    }
+
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        date.format(&self.date_format).to_string()
+    }
+
+    pub fn accent(&self) -> Color {
+        Color::from_str(&self.theme.accent).unwrap_or(Color::Green)
+    }
+
+    pub fn overdue(&self) -> Color {
+        Color::from_str(&self.theme.overdue).unwrap_or(Color::Red)
+    }
+}
+
+impl ListConfig {
+    /// Fill in the filters that were not given
+    pub fn apply(&self, mut list_arg: TaskListArg) -> TaskListArg {
+        list_arg.number = list_arg.number.or(self.number);
+        list_arg.overdue = !list_arg.no_overdue && (list_arg.overdue || self.overdue);
+        if list_arg.tags.is_empty() {
+            list_arg.tags = self.tags.clone();
+        }
+        list_arg
+    }
+}
+
+// region:    --- Keys
+
+/// Value of a dotted `key`, `None` when it is unset
+pub fn get_key(config: &Config, key: &str) -> Result<Option<toml::Value>> {
+    check_key(key)?;
+    let value = toml::Value::try_from(config).map_err(Error::custom_from_err)?;
+    Ok(key
+        .split('.')
+        .try_fold(&value, |value, part| value.get(part))
+        .cloned())
+}
+
+/// Set a dotted `key` in the content of a config file, returning the new content
+///
+/// `value` is read as a TOML value, falling back to a string, and the result is validated.
+pub fn set_key(content: &str, key: &str, value: &str) -> Result<String> {
+    check_key(key)?;
+    let mut table: toml::Table = toml::from_str(content).map_err(Error::custom_from_err)?;
+    let value = toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut t| t.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()));
+
+    let (sections, name) = match key.rsplit_once('.') {
+        Some((section, name)) => (Some(section), name),
+        None => (None, key),
+    };
+    let mut target = &mut table;
+    for section in sections.into_iter().flat_map(|s| s.split('.')) {
+        target = target
+            .entry(section)
+            .or_insert_with(|| toml::Value::Table(Default::default()))
+            .as_table_mut()
+            .ok_or_else(|| Error::custom(format!("{section} is not a table")))?;
+    }
+    target.insert(name.to_string(), value);
+
+    let content = toml::to_string_pretty(&table).map_err(Error::custom_from_err)?;
+    Config::parse(&content)
+        .map_err(|err| Error::custom(format!("Invalid value for {key}: {err}")))?;
+    Ok(content)
+}
+
+fn check_key(key: &str) -> Result<()> {
+    if !CONFIG_KEYS.contains(&key) {
+        return Err(Error::custom(format!(
+            "Unknown config key {key}, expected one of {}",
+            CONFIG_KEYS.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+// endregion: --- Keys
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    #[test]
+    fn test_parse_config() -> Result<()> {
+        // -- Setup & Fixtures
+        let content = r#"
+            date_format = "%d/%m/%Y"
+
+            [tui]
+            project_pane = 30
+
+            [list]
+            tags = ["work"]
+        "#;
+
+        // -- Exec
+        let config = Config::parse(content)?;
+
+        // -- Check
+        assert_eq!(config.tui.project_pane, 30);
+        assert_eq!(config.tui.frames_per_second, 30);
+        assert_eq!(config.list.tags, vec!["work"]);
+        let date = NaiveDate::from_ymd_opt(2025, 7, 20).ok_or("invalid date")?;
+        assert_eq!(config.format_date(date), "20/07/2025");
+
+        let list = ListConfig {
+            overdue: true,
+            ..Default::default()
+        };
+        assert!(list.apply(TaskListArg::default()).overdue);
+        let list_arg = TaskListArg {
+            no_overdue: true,
+            ..Default::default()
+        };
+        assert!(!list.apply(list_arg).overdue);
+
+        assert!(Config::parse("colour = true").is_err());
+        assert!(Config::parse("[tui]\nproject_pane = 95").is_err());
+        assert!(Config::parse("[theme]\naccent = \"greenish\"").is_err());
+        assert!(Config::parse("date_format = \"%Q\"").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_set_key() -> Result<()> {
+        // -- Setup & Fixtures
+        let content = "confirm = false\n";
+
+        // -- Exec
+        let content = set_key(content, "tui.project_pane", "25")?;
+        let content = set_key(&content, "theme.accent", "cyan")?;
+        let config = Config::parse(&content)?;
+
+        // -- Check
+        assert!(!config.confirm);
+        assert_eq!(config.tui.project_pane, 25);
+        assert_eq!(config.theme.accent, "cyan");
+        assert_eq!(
+            get_key(&config, "tui.project_pane")?,
+            Some(toml::Value::Integer(25))
+        );
+        assert_eq!(get_key(&config, "list.number")?, None);
+        assert!(get_key(&config, "tui").is_err());
+        assert!(set_key(&content, "tui.project_pane", "five").is_err());
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
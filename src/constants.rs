@@ -1 +1,9 @@
+use std::time::Duration;
+
 pub const PROJECT_INBOX_ID: i64 = 1;
+
+/// Workspace whose database is `twodo.db` in the data directory
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+/// How long a message stays in the status bar of the TUI
+pub const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
@@ -0,0 +1,66 @@
+use crate::{
+    app::Message,
+    cli::{ConfigGetArg, ConfigOp, ConfigSetArg},
+    config::{config_path, get_key, set_key, Config},
+    controller::editor::open_editor,
+    Error, Result,
+};
+use std::{io::Write, path::Path};
+
+pub(crate) fn delegate_config_op(op: ConfigOp) -> Result<Message> {
+    let path = config_path()?;
+    let mut stdout = std::io::stdout();
+    match op {
+        ConfigOp::Get(get_arg) => get_config(&path, get_arg, &mut stdout),
+        ConfigOp::Set(set_arg) => set_config(&path, set_arg),
+        ConfigOp::Path => {
+            writeln!(stdout, "{}", path.display())?;
+            Ok(Message::Noop)
+        }
+        ConfigOp::Edit => edit_config(&path),
+    }
+}
+
+fn get_config(path: &Path, get_arg: ConfigGetArg, mut writer: impl Write) -> Result<Message> {
+    let config = Config::load(path)?;
+    match get_key(&config, &get_arg.key)? {
+        // Print strings without the quotes
+        Some(toml::Value::String(value)) => writeln!(writer, "{value}")?,
+        Some(value) => writeln!(writer, "{value}")?,
+        None => {}
+    }
+    Ok(Message::Noop)
+}
+
+fn set_config(path: &Path, set_arg: ConfigSetArg) -> Result<Message> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let content = set_key(&content, &set_arg.key, &set_arg.value)?;
+
+    write_config(path, &content)?;
+    Ok(Message::Noop)
+}
+
+fn edit_config(path: &Path) -> Result<Message> {
+    if !path.exists() {
+        let content = toml::to_string_pretty(&Config::default()).map_err(Error::custom_from_err)?;
+        write_config(path, &content)?;
+    }
+
+    open_editor(path)?;
+
+    // Report mistakes right away rather than on the next start
+    Config::load(path)?;
+    Ok(Message::Noop)
+}
+
+fn write_config(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
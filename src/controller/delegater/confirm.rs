@@ -0,0 +1,78 @@
+use crate::{
+    cli::{Item, ProjectOp, TaskOp},
+    config::config,
+    controller::delegater::{project::count_project_tasks, task::count_sub_tasks},
+    Result,
+};
+use sqlx::SqlitePool;
+use std::io::{BufRead, IsTerminal, Write};
+
+/// Ask on the terminal before a delete, `false` unless the answer is yes
+///
+/// Nothing is asked when stdin is not a terminal, so scripts work without `--yes`.
+pub(crate) async fn confirm_item(db: &SqlitePool, item: &Item) -> Result<bool> {
+    if !config().confirm || !std::io::stdin().is_terminal() {
+        return Ok(true);
+    }
+    let question = match item {
+        Item::Task(TaskOp::Delete(delete_arg)) if !delete_arg.yes => {
+            task_question(db, delete_arg.id).await?
+        }
+        Item::Project(ProjectOp::Delete(delete_arg)) if !delete_arg.yes => {
+            project_question(db, delete_arg.id).await?
+        }
+        _ => None,
+    };
+    let Some(question) = question else {
+        return Ok(true);
+    };
+
+    eprint!("{question} [y/N] ");
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// `None` when there is no such task, deleting it does nothing
+async fn task_question(db: &SqlitePool, id: i64) -> Result<Option<String>> {
+    let title: Option<String> =
+        sqlx::query_scalar("SELECT title FROM tasks WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(db)
+            .await?;
+    let Some(title) = title else {
+        return Ok(None);
+    };
+    let sub_tasks = count_sub_tasks(db, id).await?;
+    Ok(Some(delete_task_question(&title, sub_tasks)))
+}
+
+async fn project_question(db: &SqlitePool, id: i64) -> Result<Option<String>> {
+    let name: Option<String> =
+        sqlx::query_scalar("SELECT name FROM projects WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(db)
+            .await?;
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    let tasks = count_project_tasks(db, id).await?;
+    Ok(Some(delete_project_question(&name, tasks)))
+}
+
+pub(crate) fn delete_task_question(title: &str, sub_tasks: usize) -> String {
+    match sub_tasks {
+        0 => format!("Delete task {title:?}?"),
+        1 => format!("Delete task {title:?} and its subtask?"),
+        n => format!("Delete task {title:?} and its {n} subtasks?"),
+    }
+}
+
+pub(crate) fn delete_project_question(name: &str, tasks: usize) -> String {
+    match tasks {
+        0 => format!("Delete project {name:?}?"),
+        1 => format!("Delete project {name:?} and its task?"),
+        n => format!("Delete project {name:?} and its {n} tasks?"),
+    }
+}
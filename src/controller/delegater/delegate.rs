@@ -1,13 +1,33 @@
-use super::item::delegate_item;
-use crate::{Result, app::App, cli::Cli, controller::init::init_db};
+use super::{
+    config::delegate_config_op, confirm::confirm_item, item::delegate_item, keys::list_keys,
+    workspace::delegate_workspace_op,
+};
+use crate::{
+    Result,
+    app::App,
+    cli::{Cli, Item},
+    controller::init::{init_db, resolve_db_path},
+};
 use sqlx::SqlitePool;
 
 pub async fn delegate(cli: Cli) -> Result<()> {
+    // These only deal with files and settings, not with a database
+    match cli.item {
+        Some(Item::Workspace(op)) => {
+            return delegate_workspace_op(op, cli.format).await.map(|_| ());
+        }
+        Some(Item::Config(op)) => return delegate_config_op(op).map(|_| ()),
+        Some(Item::Keys) => return list_keys(cli.format, std::io::stdout()).map(|_| ()),
+        _ => {}
+    }
+
     // Start TUI if no operation is specified
-    let db = init_db().await?;
+    let db_path = resolve_db_path(cli.db, cli.workspace.as_deref())?;
+    let db = init_db(&db_path).await?;
 
     match cli.item {
-        Some(op) => delegate_item(&db, op).await.map(|_| ()),
+        Some(op) if !confirm_item(&db, &op).await? => Ok(()),
+        Some(op) => delegate_item(&db, op, cli.format).await.map(|_| ()),
         None => start_tui(db).await,
     }
 }
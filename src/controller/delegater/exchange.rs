@@ -0,0 +1,356 @@
+use crate::{
+    app::Message,
+    cli::{ExchangeFormat, ExportArg, ImportArg, TaskListArg},
+    constants::PROJECT_INBOX_ID,
+    controller::delegater::{
+        journal::{journal_commit, journal_mark},
+        project::read_project,
+        tag::tag_task,
+        task::read_task,
+    },
+    exchange::{ics, markdown, parents_first, taskwarrior, todotxt, ExportTask, ImportTask},
+    Result,
+};
+use sqlx::SqlitePool;
+use std::{collections::HashMap, io::Read, path::Path};
+
+pub(crate) async fn delegate_import(
+    db: &SqlitePool,
+    import_arg: ImportArg,
+    mut writer: impl std::io::Write,
+) -> Result<Message> {
+    let content = read_input(&import_arg.file)?;
+    let tasks = match import_arg.from {
+        ExchangeFormat::Todotxt => todotxt::parse(&content),
+        ExchangeFormat::Taskwarrior => taskwarrior::parse(&content)?,
+        ExchangeFormat::Markdown => markdown::parse(&content),
+        ExchangeFormat::Ics => ics::parse(&content),
+    };
+
+    // The whole import is a single undo step
+    let mark = journal_mark(db).await?;
+    let count = import_tasks(db, tasks).await;
+    journal_commit(db, mark).await?;
+
+    writeln!(writer, "Imported {} tasks", count?)?;
+    Ok(Message::ReloadProject)
+}
+
+fn read_input(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        return Ok(content);
+    }
+    Ok(std::fs::read_to_string(path)?)
+}
+
+/// Add the tasks along with the projects they name, returning how many were added
+///
+/// Subtasks end up in the project of their parent. Tasks whose UUID is already taken are
+/// skipped, but still take their subtasks.
+pub(crate) async fn import_tasks(db: &SqlitePool, tasks: Vec<ImportTask>) -> Result<usize> {
+    let mut tx = db.begin().await?;
+    let mut project_ids: HashMap<String, i64> = read_project(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.name, p.id))
+        .collect();
+
+    let mut task_ids: HashMap<String, i64> = HashMap::new();
+    let mut count = 0;
+    for task in parents_first(tasks) {
+        // Trashed tasks hold on to their UUID too
+        if let Some(uuid) = &task.uuid {
+            let existing_id: Option<i64> =
+                sqlx::query_scalar("SELECT id FROM tasks WHERE uuid = ?1")
+                    .bind(uuid)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if let Some(id) = existing_id {
+                task_ids.extend(task.key.map(|key| (key, id)));
+                continue;
+            }
+        }
+
+        let parent_id = task.parent.as_ref().and_then(|key| task_ids.get(key)).copied();
+        let project_id = match task.project {
+            Some(name) => match project_ids.get(&name) {
+                Some(&id) => id,
+                None => {
+                    let id: i64 =
+                        sqlx::query_scalar("INSERT INTO projects (name) VALUES (?1) RETURNING id")
+                            .bind(&name)
+                            .fetch_one(&mut *tx)
+                            .await?;
+                    project_ids.insert(name, id);
+                    id
+                }
+            },
+            None => PROJECT_INBOX_ID,
+        };
+
+        let task_id: i64 = sqlx::query_scalar(
+            "INSERT INTO tasks
+                (title, description, done, project_id, parent_id, due, scheduled, priority,
+                 recurrence, created, completed, uuid, extra)
+            VALUES (?1, ?2, ?3, COALESCE((SELECT project_id FROM tasks WHERE id = ?5), ?4),
+                    ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+            RETURNING id",
+        )
+        .bind(task.title)
+        .bind(task.description.filter(|d| !d.is_empty()))
+        .bind(task.done)
+        .bind(project_id)
+        .bind(parent_id)
+        .bind(task.due)
+        .bind(task.scheduled)
+        .bind(task.priority)
+        .bind(task.recurrence)
+        .bind(task.created)
+        .bind(task.completed)
+        .bind(task.uuid)
+        .bind(task.extra.map(sqlx::types::Json))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(parent_id) = parent_id {
+            sqlx::query(
+                "UPDATE tasks
+                SET sub_task_ids = json_insert(sub_task_ids,'$[#]',?1)
+                WHERE id = ?2",
+            )
+            .bind(task_id)
+            .bind(parent_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tag_task(&mut *tx, task_id, &task.tags).await?;
+        if let Some(key) = task.key {
+            task_ids.insert(key, task_id);
+        }
+        count += 1;
+    }
+    tx.commit().await?;
+
+    Ok(count)
+}
+
+pub(crate) async fn export_tasks(
+    db: &SqlitePool,
+    export_arg: ExportArg,
+    writer: impl std::io::Write,
+) -> Result<Message> {
+    let list_arg = TaskListArg {
+        project_id: export_arg.project_id,
+        ..Default::default()
+    };
+    let tasks = read_task(db, list_arg).await?;
+    let projects: HashMap<i64, String> = read_project(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.name))
+        .collect();
+    let tasks: Vec<ExportTask> = tasks
+        .iter()
+        .map(|task| ExportTask {
+            task,
+            project: projects.get(&task.project_id).map_or("", String::as_str),
+        })
+        .collect();
+
+    match export_arg.to {
+        ExchangeFormat::Todotxt => todotxt::write(writer, &tasks)?,
+        ExchangeFormat::Taskwarrior => taskwarrior::write(writer, &tasks)?,
+        ExchangeFormat::Markdown => markdown::write(writer, &tasks)?,
+        ExchangeFormat::Ics => ics::write(writer, &tasks)?,
+    }
+    Ok(Message::Noop)
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    async fn init_db() -> Result<sqlx::SqlitePool> {
+        let db = sqlx::sqlite::SqlitePool::connect("sqlite::memory:").await?;
+        sqlx::migrate!("./migrations").run(&db).await?;
+        Ok(db)
+    }
+
+    use super::*;
+
+    async fn export(db: &SqlitePool, format: ExchangeFormat) -> Result<String> {
+        let mut output = Vec::new();
+        let export_arg = ExportArg {
+            to: format,
+            project_id: None,
+        };
+        export_tasks(db, export_arg, &mut output).await?;
+        Ok(String::from_utf8(output)?)
+    }
+
+    #[tokio::test]
+    async fn test_todotxt_round_trip() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let content = "\
+(A) 2025-07-30 Plan the trip +Summer%20Travel @deep%20work due:2025-08-01 rec:1y id:1
+Book flights +Summer%20Travel p:1
+x 2025-07-31 2025-07-30 Call mom @phone pri:C desc:Ask%20about%0Athe%20dates
+";
+
+        // -- Exec
+        let count = import_tasks(&db, todotxt::parse(content)).await?;
+
+        // -- Check
+        assert_eq!(count, 3);
+        assert_eq!(export(&db, ExchangeFormat::Todotxt).await?, content);
+        let projects = read_project(&db).await?;
+        assert_eq!(projects[1].name, "Summer Travel");
+        let tasks = read_task(&db, TaskListArg::default()).await?;
+        assert_eq!(tasks[0].tags.0, vec!["deep work"]);
+
+        // Importing again reuses the project
+        import_tasks(&db, todotxt::parse(content)).await?;
+        assert_eq!(read_project(&db).await?.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_taskwarrior_round_trip() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let content = r#"[
+{"uuid":"0b5e1c3a-1111-4000-8000-000000000001","description":"Plan the trip","status":"pending",
+ "project":"Travel","priority":"M","entry":"20250730T101500Z",
+ "depends":["0b5e1c3a-1111-4000-8000-000000000002"],
+ "annotations":[{"entry":"20250730T101600Z","description":"Ask Sam"}],
+ "estimate":"3h","urgency":4.2},
+{"uuid":"0b5e1c3a-1111-4000-8000-000000000002","description":"Book flights","status":"completed",
+ "project":"Travel","tags":["online"],"end":"20250731T090000Z"}
+]"#;
+
+        // -- Exec
+        let count = import_tasks(&db, taskwarrior::parse(content)?).await?;
+
+        // -- Check
+        assert_eq!(count, 2);
+        let exported: serde_json::Value =
+            serde_json::from_str(&export(&db, ExchangeFormat::Taskwarrior).await?)?;
+        let mut expected: serde_json::Value = serde_json::from_str(content)?;
+        expected[0].as_object_mut().ok_or("not an object")?.remove("urgency");
+        assert_eq!(exported, expected);
+
+        // Tasks are known by their UUID
+        assert_eq!(import_tasks(&db, taskwarrior::parse(content)?).await?, 0);
+        let tasks = read_task(&db, TaskListArg::default()).await?;
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1].parent_id, Some(tasks[0].id));
+
+        // Blank lines make no annotation, yet the description comes back whole
+        sqlx::query("UPDATE tasks SET description = ?1 WHERE id = ?2")
+            .bind("first\n\nsecond")
+            .bind(tasks[1].id)
+            .execute(&db)
+            .await?;
+        let exported = export(&db, ExchangeFormat::Taskwarrior).await?;
+        let fresh = init_db().await?;
+        import_tasks(&fresh, taskwarrior::parse(&exported)?).await?;
+        let tasks = read_task(&fresh, TaskListArg::default()).await?;
+        assert_eq!(tasks[1].description.as_deref(), Some("first\n\nsecond"));
+        assert_eq!(export(&fresh, ExchangeFormat::Taskwarrior).await?, exported);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_markdown_round_trip() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let content = "\
+# INBOX
+
+- [ ] Call mom
+
+# Travel
+
+- [ ] Plan the trip
+
+  Book the flights
+
+  and the hotel
+
+  - [x] Ask for days off
+  - [ ] Pack
+    - [ ] Buy a bag
+- [x] Renew the passport
+";
+
+        // -- Exec
+        let count = import_tasks(&db, markdown::parse(content)).await?;
+
+        // -- Check
+        assert_eq!(count, 6);
+        assert_eq!(export(&db, ExchangeFormat::Markdown).await?, content);
+        let tasks = read_task(&db, TaskListArg::default()).await?;
+        assert_eq!(tasks[1].sub_task_ids.0, vec![tasks[2].id, tasks[3].id]);
+        assert_eq!(tasks[4].parent_id, Some(tasks[3].id));
+        assert_eq!(read_project(&db).await?.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ics_round_trip() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let content = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//twodo//twodo//EN\r
+BEGIN:VTODO\r
+UID:0b5e1c3a-1111-4000-8000-000000000001\r
+DTSTAMP:20250801T120000Z\r
+SUMMARY:Plan the trip\\, finally\r
+DESCRIPTION:Book the flights\\nand the hotel\r
+STATUS:NEEDS-ACTION\r
+DUE;VALUE=DATE:20250815\r
+PRIORITY:3\r
+RRULE:FREQ=YEARLY\r
+CATEGORIES:summer,travel\r
+X-TWODO-PROJECT:Travel\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:0b5e1c3a-1111-4000-8000-000000000002\r
+DTSTAMP:20250801T120000Z\r
+SUMMARY:Ask for days off\r
+STATUS:COMPLETED\r
+RELATED-TO;RELTYPE=PARENT:0b5e1c3a-1111-4000-8000-000000000001\r
+X-TWODO-PROJECT:Travel\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+        // -- Exec
+        let count = import_tasks(&db, ics::parse(content)).await?;
+
+        // -- Check
+        assert_eq!(count, 2);
+        let exported = export(&db, ExchangeFormat::Ics).await?;
+        let without_stamps = |content: &str| -> Vec<String> {
+            content
+                .lines()
+                .filter(|line| !line.starts_with("DTSTAMP:"))
+                .map(str::to_string)
+                .collect()
+        };
+        assert_eq!(without_stamps(&exported), without_stamps(content));
+
+        // The same UIDs are the same tasks
+        assert_eq!(import_tasks(&db, ics::parse(&exported)).await?, 0);
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -1,14 +1,47 @@
 use crate::{
-    Result,
     app::Message,
-    cli::Item,
-    controller::delegater::{project::delegate_project_op, task::delegate_task_op},
+    cli::{Item, OutputFormat, ProjectOp, TagOp, TaskOp, TrashOp},
+    config::config,
+    controller::delegater::{
+        config::delegate_config_op,
+        exchange::{delegate_import, export_tasks},
+        journal::{delegate_journal_op, StepKind},
+        keys::list_keys,
+        project::{delegate_project_op, list_project},
+        tag::{delegate_tag_op, list_tag},
+        task::{delegate_task_op, list_task, search_highlight, search_task},
+        trash::{delegate_trash_op, list_trash},
+        workspace::delegate_workspace_op,
+    },
+    Result,
 };
 use sqlx::SqlitePool;
 
-pub async fn delegate_item(db: &SqlitePool, item: Item) -> Result<Message> {
+pub async fn delegate_item(db: &SqlitePool, item: Item, format: OutputFormat) -> Result<Message> {
+    let stdout = std::io::stdout();
     match item {
+        // List commands honour the output format
+        Item::Project(ProjectOp::List) => list_project(db, format, stdout).await,
+        Item::Task(TaskOp::List(list_arg)) => {
+            let list_arg = config().list.apply(list_arg);
+            list_task(db, list_arg, format, stdout).await
+        }
+        Item::Task(TaskOp::Search(search_arg)) => {
+            search_task(db, search_arg, format, search_highlight(), stdout).await
+        }
+        Item::Tag(TagOp::List) => list_tag(db, format, stdout).await,
+        Item::Trash(TrashOp::List) => list_trash(db, format, stdout).await,
+        Item::Keys => list_keys(format, stdout),
+        Item::Export(export_arg) => export_tasks(db, export_arg, stdout).await,
+        Item::Import(import_arg) => delegate_import(db, import_arg, stdout).await,
+
         Item::Project(project_op) => delegate_project_op(db, project_op).await,
         Item::Task(task_op) => delegate_task_op(db, task_op).await,
+        Item::Tag(tag_op) => delegate_tag_op(db, tag_op).await,
+        Item::Trash(trash_op) => delegate_trash_op(db, trash_op).await,
+        Item::Workspace(workspace_op) => delegate_workspace_op(workspace_op, format).await,
+        Item::Config(config_op) => delegate_config_op(config_op),
+        Item::Undo => delegate_journal_op(db, StepKind::Undo).await,
+        Item::Redo => delegate_journal_op(db, StepKind::Redo).await,
     }
 }
@@ -0,0 +1,266 @@
+use crate::{app::Message, Error, Result};
+use sqlx::SqlitePool;
+
+/// Kind of an undoable step, a step of one kind is replayed into a step of the other
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum StepKind {
+    Undo,
+    Redo,
+}
+
+impl StepKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            StepKind::Undo => "undo",
+            StepKind::Redo => "redo",
+        }
+    }
+
+    fn inverse(self) -> Self {
+        match self {
+            StepKind::Undo => StepKind::Redo,
+            StepKind::Redo => StepKind::Undo,
+        }
+    }
+}
+
+/// Last statement in the undo log, taken before a change to find what it logged
+pub(crate) async fn journal_mark(db: &SqlitePool) -> Result<i64> {
+    sqlx::query_scalar("SELECT COALESCE(MAX(seq), 0) FROM undolog")
+        .fetch_one(db)
+        .await
+        .map_err(Into::into)
+}
+
+/// Turn the statements logged since `mark` into an undo step
+///
+/// A new change makes the undone steps unreachable, so they are dropped.
+pub(crate) async fn journal_commit(db: &SqlitePool, mark: i64) -> Result<()> {
+    let mut tx = db.begin().await?;
+    let last_seq: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(seq), 0) FROM undolog")
+        .fetch_one(&mut *tx)
+        .await?;
+    if last_seq == mark {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "DELETE FROM undolog
+        WHERE seq IN (
+            SELECT undolog.seq
+            FROM undolog
+            INNER JOIN undo_steps
+                ON undolog.seq BETWEEN undo_steps.first_seq AND undo_steps.last_seq
+            WHERE undo_steps.kind = 'redo'
+        )",
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query("DELETE FROM undo_steps WHERE kind = 'redo'")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("INSERT INTO undo_steps (kind, first_seq, last_seq) VALUES ('undo', ?1, ?2)")
+        .bind(mark + 1)
+        .bind(last_seq)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Undo or redo the latest change, failing when there is none
+pub(crate) async fn delegate_journal_op(db: &SqlitePool, kind: StepKind) -> Result<Message> {
+    if !replay_step(db, kind).await? {
+        return Err(Error::custom(format!("Nothing to {}", kind.as_str())));
+    }
+    Ok(Message::ReloadProject)
+}
+
+/// Replay the latest step of `kind`, which logs the step that reverts the replay
+///
+/// Returns false when there is no step to replay.
+pub(crate) async fn replay_step(db: &SqlitePool, kind: StepKind) -> Result<bool> {
+    let mut tx = db.begin().await?;
+    let step: Option<(i64, i64, i64)> = sqlx::query_as(
+        "SELECT id, first_seq, last_seq
+        FROM undo_steps
+        WHERE kind = ?1
+        ORDER BY id DESC
+        LIMIT 1",
+    )
+    .bind(kind.as_str())
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some((step_id, first_seq, last_seq)) = step else {
+        return Ok(false);
+    };
+
+    let mark: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(seq), 0) FROM undolog")
+        .fetch_one(&mut *tx)
+        .await?;
+    let statements: Vec<String> = sqlx::query_scalar(
+        "SELECT sql FROM undolog WHERE seq BETWEEN ?1 AND ?2 ORDER BY seq DESC",
+    )
+    .bind(first_seq)
+    .bind(last_seq)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // Rows come back in reverse, so a task may be restored before its project
+    sqlx::query("PRAGMA defer_foreign_keys = ON")
+        .execute(&mut *tx)
+        .await?;
+    for statement in statements {
+        sqlx::query(&statement).execute(&mut *tx).await?;
+    }
+
+    sqlx::query("DELETE FROM undolog WHERE seq BETWEEN ?1 AND ?2")
+        .bind(first_seq)
+        .bind(last_seq)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM undo_steps WHERE id = ?1")
+        .bind(step_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        "INSERT INTO undo_steps (kind, first_seq, last_seq)
+        SELECT ?1, ?2, MAX(seq) FROM undolog",
+    )
+    .bind(kind.inverse().as_str())
+    .bind(mark + 1)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+    use crate::{
+        cli::{ProjectAddArg, ProjectDeleteArg, ProjectOp, TaskAddArg, TaskEditArg, TaskOp},
+        controller::delegater::{delegate_project_op, delegate_task_op},
+        objects::Task,
+    };
+
+    async fn init_db() -> Result<sqlx::SqlitePool> {
+        let db = sqlx::sqlite::SqlitePool::connect("sqlite::memory:").await?;
+        // create table
+        sqlx::migrate!("./migrations").run(&db).await?;
+        Ok(db)
+    }
+
+    async fn titles(db: &SqlitePool) -> Result<Vec<String>> {
+        let titles = sqlx::query_scalar("SELECT title FROM tasks WHERE deleted_at IS NULL ORDER BY id")
+            .fetch_all(db)
+            .await?;
+        Ok(titles)
+    }
+
+    #[tokio::test]
+    async fn test_undo_redo_task() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let op = TaskOp::Add(TaskAddArg {
+            title: "first".to_string(),
+            tags: vec!["home".to_string()],
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+        let op = TaskOp::Edit(TaskEditArg {
+            id: 1,
+            title: Some("renamed".to_string()),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Exec & Check
+        replay_step(&db, StepKind::Undo).await?;
+        assert_eq!(titles(&db).await?, vec!["first"]);
+
+        replay_step(&db, StepKind::Undo).await?;
+        assert!(titles(&db).await?.is_empty());
+        assert!(!replay_step(&db, StepKind::Undo).await?);
+
+        replay_step(&db, StepKind::Redo).await?;
+        replay_step(&db, StepKind::Redo).await?;
+        assert_eq!(titles(&db).await?, vec!["renamed"]);
+        let tags: Vec<String> = sqlx::query_scalar(
+            "SELECT tags.name FROM task_tags INNER JOIN tags ON task_tags.tag_id = tags.id",
+        )
+        .fetch_all(&db)
+        .await?;
+        assert_eq!(tags, vec!["home"]);
+        assert!(!replay_step(&db, StepKind::Redo).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_undo_cascade_delete() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let op = ProjectOp::Add(ProjectAddArg {
+            name: "Work".to_string(),
+        });
+        delegate_project_op(&db, op).await?;
+        for (title, parent_id) in [("parent", None), ("child", Some(1))] {
+            let op = TaskOp::Add(TaskAddArg {
+                title: title.to_string(),
+                project_id: 2,
+                parent_id,
+                ..Default::default()
+            });
+            delegate_task_op(&db, op).await?;
+        }
+
+        // -- Exec
+        delegate_project_op(&db, ProjectOp::Delete(ProjectDeleteArg { id: 2, yes: true })).await?;
+        assert!(titles(&db).await?.is_empty());
+        replay_step(&db, StepKind::Undo).await?;
+
+        // -- Check
+        assert_eq!(titles(&db).await?, vec!["parent", "child"]);
+        let parent: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(parent.sub_task_ids.to_vec(), vec![2]);
+        assert_eq!(parent.project_id, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_new_change_clears_redo() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        for title in ["first", "second"] {
+            let op = TaskOp::Add(TaskAddArg {
+                title: title.to_string(),
+                ..Default::default()
+            });
+            delegate_task_op(&db, op).await?;
+        }
+
+        // -- Exec
+        replay_step(&db, StepKind::Undo).await?;
+        let op = TaskOp::Add(TaskAddArg {
+            title: "third".to_string(),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Check
+        assert!(!replay_step(&db, StepKind::Redo).await?);
+        replay_step(&db, StepKind::Undo).await?;
+        assert_eq!(titles(&db).await?, vec!["first"]);
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
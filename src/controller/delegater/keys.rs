@@ -0,0 +1,12 @@
+use crate::{
+    app::Message, cli::OutputFormat, config::config, controller::output::write_records,
+    keymap::Keymap, Error, Result,
+};
+
+/// Print the key bindings in use, the defaults with the config applied
+pub(crate) fn list_keys(format: OutputFormat, writer: impl std::io::Write) -> Result<Message> {
+    let keymap = Keymap::new(&config().keys).map_err(Error::custom)?;
+    write_records(writer, format, &keymap.bindings())?;
+
+    Ok(Message::Noop)
+}
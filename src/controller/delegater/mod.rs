@@ -1,12 +1,23 @@
 // region:    --- Modules
+mod config;
+mod confirm;
 mod delegate;
+mod exchange;
 mod item;
+mod journal;
+mod keys;
 mod project;
+mod tag;
 mod task;
+mod trash;
+mod workspace;
 
 // -- Flatten
-pub(crate) use task::{delegate_task_op, read_task};
-pub(crate) use project::{delegate_project_op, read_project};
+pub(crate) use task::{count_sub_tasks, delegate_task_op, read_task};
+pub(crate) use project::{count_project_tasks, delegate_project_op, read_project};
+pub(crate) use confirm::{delete_project_question, delete_task_question};
+pub(crate) use journal::{replay_step, StepKind};
+pub(crate) use trash::{delegate_trash_op, read_trash};
 pub use delegate::delegate;
 pub use item::delegate_item;
 
@@ -1,32 +1,42 @@
 use crate::{
     app::Message,
-    cli::{ProjectAddArg, ProjectDeleteArg, ProjectEditArg, ProjectOp},
+    cli::{OutputFormat, ProjectAddArg, ProjectDeleteArg, ProjectEditArg, ProjectOp},
+    controller::{
+        delegater::journal::{journal_commit, journal_mark},
+        output::write_records,
+    },
     objects::Project,
     Result,
 };
 use sqlx::SqlitePool;
 
 pub(crate) async fn delegate_project_op(db: &SqlitePool, op: ProjectOp) -> Result<Message> {
-    match op {
-        ProjectOp::List => list_project(db, std::io::stdout()).await,
+    let mark = journal_mark(db).await?;
+    let message = match op {
+        ProjectOp::List => list_project(db, OutputFormat::Plain, std::io::stdout()).await,
         ProjectOp::Add(add_arg) => add_project(db, add_arg).await,
         ProjectOp::Edit(project_edit_arg) => edit_project(db, project_edit_arg).await,
         ProjectOp::Delete(project_delete_arg) => delete_project(db, project_delete_arg).await,
-    }
+    };
+    journal_commit(db, mark).await?;
+
+    message
 }
 
 pub async fn read_project(db: &SqlitePool) -> Result<Vec<Project>> {
-    sqlx::query_as("SELECT * FROM projects")
+    sqlx::query_as("SELECT * FROM projects WHERE deleted_at IS NULL")
         .fetch_all(db)
         .await
         .map_err(Into::into)
 }
 
-async fn list_project(db: &SqlitePool, mut writer: impl std::io::Write) -> Result<Message> {
+pub(crate) async fn list_project(
+    db: &SqlitePool,
+    format: OutputFormat,
+    writer: impl std::io::Write,
+) -> Result<Message> {
     let projects = read_project(db).await?;
-    for project in projects {
-        writeln!(writer, "{}. {}", project.id, project.name)?;
-    }
+    write_records(writer, format, &projects)?;
 
     Ok(Message::Noop)
 }
@@ -49,18 +59,48 @@ async fn edit_project(db: &SqlitePool, edit_arg: ProjectEditArg) -> Result<Messa
         .bind(edit_arg.id)
         .execute(db)
         .await?;
-    Ok(Message::Noop)
+    Ok(Message::ReloadProject)
 }
 
+/// Move a project and all of its tasks to the trash
 async fn delete_project(db: &SqlitePool, edit_arg: ProjectDeleteArg) -> Result<Message> {
-    sqlx::query("DELETE FROM projects WHERE id = ?1")
+    let mut tx = db.begin().await?;
+    let deleted_at: Option<String> = sqlx::query_scalar(
+        "UPDATE projects
+        SET deleted_at = strftime('%Y-%m-%d %H:%M:%f', 'now')
+        WHERE id = ?1 AND deleted_at IS NULL
+        RETURNING deleted_at",
+    )
+    .bind(edit_arg.id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    // Tasks share the timestamp of the project, so restoring it brings back only these tasks
+    if let Some(deleted_at) = deleted_at {
+        sqlx::query(
+            "UPDATE tasks SET deleted_at = ?1 WHERE project_id = ?2 AND deleted_at IS NULL",
+        )
+        .bind(deleted_at)
         .bind(edit_arg.id)
-        .execute(db)
+        .execute(&mut *tx)
         .await?;
+    }
+    tx.commit().await?;
 
     Ok(Message::ReloadProject)
 }
 
+/// Number of tasks that go to the trash along with the project
+pub(crate) async fn count_project_tasks(db: &SqlitePool, id: i64) -> Result<usize> {
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM tasks WHERE project_id = ?1 AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_one(db)
+    .await?;
+    Ok(count as usize)
+}
+
 // region:    --- Tests
 
 #[cfg(test)]
@@ -117,9 +157,10 @@ mod tests {
             id: edited_project_id,
             name: edited_project_name.to_string(),
         });
-        delegate_project_op(&db, edit_arg).await?;
+        let message = delegate_project_op(&db, edit_arg).await?;
 
         // -- Check
+        assert_eq!(message, Message::ReloadProject);
         let project: Project = sqlx::query_as("SELECT * FROM projects WHERE name = ?1")
             .bind(edited_project_name)
             .fetch_one(&db)
@@ -142,16 +183,18 @@ mod tests {
 
         // -- Exec
         let project_id = 2;
-        let delete_arg = ProjectOp::Delete(ProjectDeleteArg { id: project_id });
+        let delete_arg = ProjectOp::Delete(ProjectDeleteArg { id: project_id, yes: true });
         delegate_project_op(&db, delete_arg).await?;
 
         // -- Check
-        let task: Option<Project> = sqlx::query_as("SELECT * FROM projects WHERE name = ?1")
-            .bind(project_name)
-            .fetch_optional(&db)
-            .await?;
-
-        assert_eq!(None, task);
+        let deleted_at: Option<String> =
+            sqlx::query_scalar("SELECT deleted_at FROM projects WHERE name = ?1")
+                .bind(project_name)
+                .fetch_one(&db)
+                .await?;
+
+        assert!(deleted_at.is_some());
+        assert!(read_project(&db).await?.iter().all(|p| p.id != project_id));
         Ok(())
     }
 }
@@ -0,0 +1,282 @@
+use crate::{
+    app::Message,
+    cli::{OutputFormat, TagAddArg, TagDeleteArg, TagMergeArg, TagOp, TagRenameArg},
+    controller::{
+        delegater::journal::{journal_commit, journal_mark},
+        output::write_records,
+    },
+    objects::Tag,
+    Error, Result,
+};
+use sqlx::{Acquire, Executor, Sqlite, SqlitePool};
+
+pub(crate) async fn delegate_tag_op(db: &SqlitePool, op: TagOp) -> Result<Message> {
+    let mark = journal_mark(db).await?;
+    let message = match op {
+        TagOp::List => list_tag(db, OutputFormat::Plain, std::io::stdout()).await,
+        TagOp::Add(add_arg) => add_tag(db, add_arg).await,
+        TagOp::Rename(rename_arg) => rename_tag(db, rename_arg).await,
+        TagOp::Delete(delete_arg) => delete_tag(db, delete_arg).await,
+        TagOp::Merge(merge_arg) => merge_tag(db, merge_arg).await,
+    };
+    journal_commit(db, mark).await?;
+
+    message
+}
+
+pub async fn read_tag(db: &SqlitePool) -> Result<Vec<Tag>> {
+    sqlx::query_as("SELECT * FROM tags ORDER BY name")
+        .fetch_all(db)
+        .await
+        .map_err(Into::into)
+}
+
+/// Fail unless the task exists and is not in the trash
+async fn check_live_task<'c>(db: impl Executor<'c, Database = Sqlite>, task_id: i64) -> Result<()> {
+    let task_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM tasks WHERE id = ?1 AND deleted_at IS NULL)",
+    )
+    .bind(task_id)
+    .fetch_one(db)
+    .await?;
+    if !task_exists {
+        return Err(Error::custom(format!("Task {task_id} does not exist")));
+    }
+    Ok(())
+}
+
+/// Attach tags to a task by name, creating the tags that do not exist yet
+///
+/// Takes a pool or a transaction.
+pub(crate) async fn tag_task<'c>(
+    db: impl Acquire<'c, Database = Sqlite>,
+    task_id: i64,
+    tags: &[String],
+) -> Result<()> {
+    let mut conn = db.acquire().await?;
+    if !tags.is_empty() {
+        check_live_task(&mut *conn, task_id).await?;
+    }
+    for tag in tags {
+        let name = normalize_tag(tag)?;
+        sqlx::query("INSERT OR IGNORE INTO tags (name) VALUES (?1)")
+            .bind(name)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO task_tags (task_id, tag_id)
+            SELECT ?1, id FROM tags WHERE name = ?2",
+        )
+        .bind(task_id)
+        .bind(name)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Detach tags from a task by name
+pub(crate) async fn untag_task(db: &SqlitePool, task_id: i64, tags: &[String]) -> Result<()> {
+    if !tags.is_empty() {
+        check_live_task(db, task_id).await?;
+    }
+    for tag in tags {
+        sqlx::query(
+            "DELETE FROM task_tags
+            WHERE task_id = ?1
+              AND tag_id = (SELECT id FROM tags WHERE name = ?2)",
+        )
+        .bind(task_id)
+        .bind(normalize_tag(tag)?)
+        .execute(db)
+        .await?;
+    }
+    Ok(())
+}
+
+fn normalize_tag(tag: &str) -> Result<&str> {
+    let name = tag.trim();
+    if name.is_empty() {
+        return Err(Error::custom("Tag name cannot be empty"));
+    }
+    Ok(name)
+}
+
+pub(crate) async fn list_tag(
+    db: &SqlitePool,
+    format: OutputFormat,
+    writer: impl std::io::Write,
+) -> Result<Message> {
+    let tags = read_tag(db).await?;
+    write_records(writer, format, &tags)?;
+
+    Ok(Message::Noop)
+}
+
+async fn add_tag(db: &SqlitePool, add_arg: TagAddArg) -> Result<Message> {
+    sqlx::query("INSERT INTO tags (name) VALUES (?1)")
+        .bind(normalize_tag(&add_arg.name)?)
+        .execute(db)
+        .await?;
+
+    Ok(Message::ReloadTask)
+}
+
+async fn rename_tag(db: &SqlitePool, rename_arg: TagRenameArg) -> Result<Message> {
+    sqlx::query("UPDATE tags SET name = ?1 WHERE id = ?2")
+        .bind(normalize_tag(&rename_arg.name)?)
+        .bind(rename_arg.id)
+        .execute(db)
+        .await?;
+
+    Ok(Message::ReloadTask)
+}
+
+async fn delete_tag(db: &SqlitePool, delete_arg: TagDeleteArg) -> Result<Message> {
+    sqlx::query("DELETE FROM tags WHERE id = ?1")
+        .bind(delete_arg.id)
+        .execute(db)
+        .await?;
+
+    Ok(Message::ReloadTask)
+}
+
+async fn merge_tag(db: &SqlitePool, merge_arg: TagMergeArg) -> Result<Message> {
+    if merge_arg.from == merge_arg.into {
+        return Err(Error::custom("Cannot merge a tag into itself"));
+    }
+
+    let mut tx = db.begin().await?;
+    sqlx::query(
+        "INSERT OR IGNORE INTO task_tags (task_id, tag_id)
+        SELECT task_id, ?2 FROM task_tags WHERE tag_id = ?1",
+    )
+    .bind(merge_arg.from)
+    .bind(merge_arg.into)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query("DELETE FROM tags WHERE id = ?1")
+        .bind(merge_arg.from)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Message::ReloadTask)
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    async fn init_db() -> Result<sqlx::SqlitePool> {
+        let db = sqlx::sqlite::SqlitePool::connect("sqlite::memory:").await?;
+        // create table
+        sqlx::migrate!("./migrations").run(&db).await?;
+        Ok(db)
+    }
+
+    async fn add_task(db: &SqlitePool, title: &str) -> Result<i64> {
+        let id = sqlx::query_scalar("INSERT INTO tasks (title) VALUES (?1) RETURNING id")
+            .bind(title)
+            .fetch_one(db)
+            .await?;
+        Ok(id)
+    }
+
+    async fn task_tag_names(db: &SqlitePool, task_id: i64) -> Result<Vec<String>> {
+        let names = sqlx::query_scalar(
+            "SELECT t.name
+            FROM task_tags AS tt
+            INNER JOIN tags AS t
+                ON tt.tag_id = t.id
+            WHERE tt.task_id = ?1
+            ORDER BY t.name",
+        )
+        .bind(task_id)
+        .fetch_all(db)
+        .await?;
+        Ok(names)
+    }
+
+    #[tokio::test]
+    async fn test_tag_task() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let task_id = add_task(&db, "Test tag task").await?;
+
+        // -- Exec
+        let tags = ["bug".to_string(), " waiting-on ".to_string(), "bug".to_string()];
+        tag_task(&db, task_id, &tags).await?;
+        untag_task(&db, task_id, &["bug".to_string()]).await?;
+
+        // -- Check
+        assert_eq!(task_tag_names(&db, task_id).await?, vec!["waiting-on"]);
+        let tags = read_tag(&db).await?;
+        assert_eq!(tags.len(), 2);
+
+        // Trashed tasks keep their tags
+        sqlx::query("UPDATE tasks SET deleted_at = 'now' WHERE id = ?1")
+            .bind(task_id)
+            .execute(&db)
+            .await?;
+        let tags = ["bug".to_string()];
+        assert!(tag_task(&db, task_id, &tags).await.is_err());
+        assert!(untag_task(&db, task_id, &["waiting-on".to_string()]).await.is_err());
+        assert_eq!(task_tag_names(&db, task_id).await?, vec!["waiting-on"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_merge_tag() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let first_task_id = add_task(&db, "first task").await?;
+        let second_task_id = add_task(&db, "second task").await?;
+        tag_task(&db, first_task_id, &["bugs".to_string()]).await?;
+        tag_task(&db, second_task_id, &["bug".to_string(), "bugs".to_string()]).await?;
+        let tags = read_tag(&db).await?;
+        let (bug, bugs) = (tags[0].id, tags[1].id);
+
+        // -- Exec
+        let op = TagOp::Merge(TagMergeArg {
+            from: bugs,
+            into: bug,
+        });
+        delegate_tag_op(&db, op).await?;
+
+        // -- Check
+        assert_eq!(task_tag_names(&db, first_task_id).await?, vec!["bug"]);
+        assert_eq!(task_tag_names(&db, second_task_id).await?, vec!["bug"]);
+        assert_eq!(read_tag(&db).await?, vec![Tag { id: bug, name: "bug".to_string() }]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rename_and_delete_tag() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let task_id = add_task(&db, "Test rename tag").await?;
+        tag_task(&db, task_id, &["wip".to_string()]).await?;
+        let tag_id = read_tag(&db).await?[0].id;
+
+        // -- Exec
+        let op = TagOp::Rename(TagRenameArg {
+            id: tag_id,
+            name: "in-progress".to_string(),
+        });
+        delegate_tag_op(&db, op).await?;
+        let renamed = task_tag_names(&db, task_id).await?;
+        delegate_tag_op(&db, TagOp::Delete(TagDeleteArg { id: tag_id })).await?;
+
+        // -- Check
+        assert_eq!(renamed, vec!["in-progress"]);
+        assert!(task_tag_names(&db, task_id).await?.is_empty());
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
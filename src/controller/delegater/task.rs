@@ -1,41 +1,119 @@
 use crate::{
     app::Message,
-    cli::{TaskAddArg, TaskDeleteArg, TaskDoneArg, TaskEditArg, TaskListArg, TaskOp},
-    objects::Task,
-    Result,
+    cli::{
+        OutputFormat, ReorderDirection, TaskAddArg, TaskDeleteArg, TaskDoneArg, TaskEditArg,
+        TaskFoldArg, TaskListArg, TaskMoveArg, TaskOp, TaskReorderArg, TaskSearchArg,
+    },
+    controller::{
+        delegater::{
+            journal::{journal_commit, journal_mark},
+            project::read_project,
+            tag::{tag_task, untag_task},
+        },
+        editor::edit_task_text,
+        output::{write_records, TaskRecord},
+    },
+    objects::{Recurrence, Task},
+    Error, Result,
+};
+use chrono::{Local, NaiveDate};
+use sqlx::{Executor, Sqlite, SqlitePool};
+use std::{
+    collections::{HashMap, VecDeque},
+    io::IsTerminal,
 };
-use sqlx::SqlitePool;
 
 pub(crate) async fn delegate_task_op(db: &SqlitePool, op: TaskOp) -> Result<Message> {
-    match op {
-        TaskOp::List(list_arg) => list_task(db, list_arg, &mut std::io::stdout()).await,
+    // Changes are recorded in the undo journal, even when the operation fails halfway
+    let mark = journal_mark(db).await?;
+    let message = match op {
+        TaskOp::List(list_arg) => {
+            list_task(db, list_arg, OutputFormat::Plain, std::io::stdout()).await
+        }
         TaskOp::Add(add_arg) => add_task(db, add_arg).await,
         TaskOp::Edit(edit_arg) => edit_task(db, edit_arg).await,
         TaskOp::Done(done_arg) => done_task(db, done_arg).await,
         TaskOp::Delete(delete_arg) => delete_task(db, delete_arg).await,
+        TaskOp::Move(move_arg) => move_task(db, move_arg).await,
+        TaskOp::Reorder(reorder_arg) => reorder_task(db, reorder_arg).await,
+        TaskOp::Fold(fold_arg) => fold_task(db, fold_arg).await,
         TaskOp::UnDone(undone_arg) => undone_task(db, undone_arg).await,
+        TaskOp::Search(search_arg) => {
+            search_task(
+                db,
+                search_arg,
+                OutputFormat::Plain,
+                search_highlight(),
+                std::io::stdout(),
+            )
+            .await
+        }
+    };
+    journal_commit(db, mark).await?;
+
+    message
+}
+
+/// A task that is not in the trash
+async fn read_live_task<'c>(db: impl Executor<'c, Database = Sqlite>, id: i64) -> Result<Task> {
+    sqlx::query_as("SELECT * FROM tasks WHERE id = ?1 AND deleted_at IS NULL")
+        .bind(id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| Error::custom(format!("Task {id} does not exist")))
+}
+
+/// Fail unless the project exists and is not in the trash
+async fn check_live_project<'c>(
+    db: impl Executor<'c, Database = Sqlite>,
+    project_id: i64,
+) -> Result<()> {
+    let project_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM projects WHERE id = ?1 AND deleted_at IS NULL)",
+    )
+    .bind(project_id)
+    .fetch_one(db)
+    .await?;
+    if !project_exists {
+        return Err(Error::custom(format!("Project {project_id} does not exist")));
+    }
+    Ok(())
+}
+
+/// Keep the day of the due date that the recurrence starts from, see [`Recurrence::anchored`]
+fn anchor_recurrence(recurrence: Recurrence, due: Option<NaiveDate>) -> Recurrence {
+    match due {
+        Some(due) => recurrence.anchored(due),
+        None => recurrence,
     }
 }
 
 async fn add_task(db: &SqlitePool, add_arg: TaskAddArg) -> Result<Message> {
-    let query_str = if add_arg.parent_id.is_some() {
-        "INSERT INTO tasks (title, description, project_id, parent_id)
-        SELECT ?1, ?2, project_id, id as parent_id
-        FROM tasks
-        WHERE id = ?4
-        RETURNING id"
-    } else {
-        "INSERT INTO tasks (title, description, project_id, parent_id)
-        VALUES (?1, ?2, ?3, ?4)
-        RETURNING id"
+    // Subtasks go to the project of their parent
+    let project_id = match add_arg.parent_id {
+        Some(parent_id) => read_live_task(db, parent_id).await?.project_id,
+        None => {
+            check_live_project(db, add_arg.project_id).await?;
+            add_arg.project_id
+        }
     };
-    let task_id: i64 = sqlx::query_scalar(query_str)
-        .bind(add_arg.title)
-        .bind(add_arg.description)
-        .bind(add_arg.project_id)
-        .bind(add_arg.parent_id)
-        .fetch_one(db)
-        .await?;
+    let task_id: i64 = sqlx::query_scalar(
+        "INSERT INTO tasks
+            (title, description, project_id, parent_id, due, scheduled, priority, recurrence,
+             created)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, date('now', 'localtime'))
+        RETURNING id",
+    )
+    .bind(add_arg.title)
+    .bind(add_arg.description)
+    .bind(project_id)
+    .bind(add_arg.parent_id)
+    .bind(add_arg.due)
+    .bind(add_arg.scheduled)
+    .bind(add_arg.priority)
+    .bind(add_arg.recur.map(|r| anchor_recurrence(r, add_arg.due).to_string()))
+    .fetch_one(db)
+    .await?;
 
     if let Some(parent_id) = add_arg.parent_id {
         sqlx::query(
@@ -48,13 +126,22 @@ async fn add_task(db: &SqlitePool, add_arg: TaskAddArg) -> Result<Message> {
         .execute(db)
         .await?;
     };
+    tag_task(db, task_id, &add_arg.tags).await?;
 
     Ok(Message::ReloadTask)
 }
 
 pub async fn read_task(db: &SqlitePool, list_arg: TaskListArg) -> Result<Vec<Task>> {
-    let mut query_str = "SELECT * FROM tasks".to_string();
-    let mut where_clauses = Vec::new();
+    let mut query_str = "SELECT *,
+        (SELECT json_group_array(name)
+         FROM (SELECT tags.name
+               FROM task_tags
+               INNER JOIN tags ON task_tags.tag_id = tags.id
+               WHERE task_tags.task_id = tasks.id
+               ORDER BY tags.name)) AS tags
+        FROM tasks"
+        .to_string();
+    let mut where_clauses = vec!["deleted_at IS NULL"];
     let mut args = Vec::new();
 
     if let Some(project_id) = list_arg.project_id {
@@ -62,6 +149,35 @@ pub async fn read_task(db: &SqlitePool, list_arg: TaskListArg) -> Result<Vec<Tas
         args.push(project_id.to_string());
     }
 
+    if let Some(due_before) = list_arg.due_before {
+        where_clauses.push("due < ?");
+        args.push(due_before.to_string());
+    }
+
+    if let Some(scheduled_before) = list_arg.scheduled_before {
+        where_clauses.push("scheduled < ?");
+        args.push(scheduled_before.to_string());
+    }
+
+    if list_arg.overdue {
+        where_clauses.push("done = false AND due < date('now', 'localtime')");
+    }
+
+    for tag in list_arg.tags {
+        where_clauses.push(
+            "id IN (SELECT task_tags.task_id
+                    FROM task_tags
+                    INNER JOIN tags ON task_tags.tag_id = tags.id
+                    WHERE tags.name = ?)",
+        );
+        args.push(tag);
+    }
+
+    if let Some(search) = list_arg.search.as_deref().and_then(fts_query) {
+        where_clauses.push("id IN (SELECT rowid FROM tasks_fts WHERE tasks_fts MATCH ?)");
+        args.push(search);
+    }
+
     let where_str = where_clauses.join(" AND ");
 
     if !where_str.is_empty() {
@@ -81,21 +197,136 @@ pub async fn read_task(db: &SqlitePool, list_arg: TaskListArg) -> Result<Vec<Tas
     query.fetch_all(db).await.map_err(Into::into)
 }
 
-async fn list_task<T: std::io::Write>(
+pub(crate) async fn list_task<T: std::io::Write>(
     db: &SqlitePool,
-    _list_arg: TaskListArg,
+    list_arg: TaskListArg,
+    format: OutputFormat,
+    writer: T,
+) -> Result<Message> {
+    let tasks: Vec<Task> = read_task(db, list_arg).await?;
+    write_tasks(db, &tasks, format, writer).await?;
+
+    Ok(Message::Noop)
+}
+
+/// Write tasks along with the names of their projects
+async fn write_tasks<T: std::io::Write>(
+    db: &SqlitePool,
+    tasks: &[Task],
+    format: OutputFormat,
+    writer: T,
+) -> Result<()> {
+    let projects = read_project(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.name))
+        .collect::<HashMap<_, _>>();
+    let records = tasks
+        .iter()
+        .map(|task| TaskRecord {
+            task,
+            project: projects.get(&task.project_id).map_or("", String::as_str),
+        })
+        .collect::<Vec<_>>();
+    write_records(writer, format, &records)
+}
+
+/// Markers around search matches, coloured when printing to a terminal
+pub(crate) fn search_highlight() -> (&'static str, &'static str) {
+    if std::io::stdout().is_terminal() {
+        ("\x1b[1;33m", "\x1b[0m")
+    } else {
+        ("[", "]")
+    }
+}
+
+/// Turn free text into an FTS5 query matching every word as a prefix
+///
+/// Returns `None` when there is nothing to search for.
+pub(crate) fn fts_query(text: &str) -> Option<String> {
+    let terms = text
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+pub(crate) async fn search_task<T: std::io::Write>(
+    db: &SqlitePool,
+    search_arg: TaskSearchArg,
+    format: OutputFormat,
+    (open, close): (&str, &str),
     mut writer: T,
 ) -> Result<Message> {
-    let tasks: Vec<Task> = read_task(db, _list_arg).await?;
+    let Some(query) = fts_query(&search_arg.query) else {
+        return Ok(Message::Noop);
+    };
+    let (open, close) = match format {
+        OutputFormat::Plain => (open, close),
+        _ => ("", ""),
+    };
+
+    let mut query_str = "SELECT tasks.id,
+            highlight(tasks_fts, 0, ?1, ?2) AS title,
+            snippet(tasks_fts, 1, ?1, ?2, '…', 12) AS snippet
+        FROM tasks_fts
+        INNER JOIN tasks ON tasks.id = tasks_fts.rowid
+        WHERE tasks_fts MATCH ?3 AND tasks.deleted_at IS NULL"
+        .to_string();
+    if search_arg.project_id.is_some() {
+        query_str.push_str(" AND tasks.project_id = ?4");
+    }
+    query_str.push_str(" ORDER BY rank");
+    if search_arg.number.is_some() {
+        query_str.push_str(" LIMIT ?5");
+    }
+
+    let matches: Vec<(i64, String, Option<String>)> = sqlx::query_as(&query_str)
+        .bind(open)
+        .bind(close)
+        .bind(&query)
+        .bind(search_arg.project_id)
+        .bind(search_arg.number.map(|n| n as i64))
+        .fetch_all(db)
+        .await?;
 
-    for task in tasks {
-        writeln!(writer, "{}. {}", task.id, task.title)?;
+    if format != OutputFormat::Plain {
+        // Structured output has the full tasks, still in order of relevance
+        let rank = matches
+            .iter()
+            .enumerate()
+            .map(|(i, (id, _, _))| (*id, i))
+            .collect::<HashMap<_, _>>();
+        let list_arg = TaskListArg {
+            search: Some(search_arg.query),
+            ..Default::default()
+        };
+        let mut tasks = read_task(db, list_arg).await?;
+        tasks.retain(|t| rank.contains_key(&t.id));
+        tasks.sort_by_key(|t| rank[&t.id]);
+        write_tasks(db, &tasks, format, writer).await?;
+        return Ok(Message::Noop);
+    }
+
+    for (id, title, snippet) in matches {
+        writeln!(writer, "{id}. {title}")?;
+        if let Some(snippet) = snippet.filter(|s| s.contains(open)) {
+            writeln!(writer, "    {snippet}")?;
+        }
     }
 
     Ok(Message::Noop)
 }
 
-async fn edit_task(db: &SqlitePool, edit_arg: TaskEditArg) -> Result<Message> {
+async fn edit_task(db: &SqlitePool, mut edit_arg: TaskEditArg) -> Result<Message> {
+    let task = read_live_task(db, edit_arg.id).await?;
+    if edit_arg.editor {
+        let description = task.description.as_deref().unwrap_or_default();
+        let (title, description) = edit_task_text(&task.title, description)?;
+        edit_arg.title = Some(title);
+        edit_arg.description = Some(description);
+    }
+
     let mut query_str = "UPDATE tasks SET ".to_string();
     let mut args = Vec::new();
     let mut set_clauses = Vec::new();
@@ -106,43 +337,417 @@ async fn edit_task(db: &SqlitePool, edit_arg: TaskEditArg) -> Result<Message> {
     }
 
     if let Some(description) = edit_arg.description {
-        set_clauses.push("description = ?");
+        set_clauses.push("description = NULLIF(?, '')");
         args.push(description);
     }
 
-    query_str.push_str(&set_clauses.join(", "));
-    query_str.push_str(" WHERE id = ?");
-    args.push(edit_arg.id.to_string());
+    if let Some(due) = edit_arg.due {
+        set_clauses.push("due = ?");
+        args.push(due.to_string());
+    } else if edit_arg.clear_due {
+        set_clauses.push("due = NULL");
+    }
+
+    if let Some(scheduled) = edit_arg.scheduled {
+        set_clauses.push("scheduled = ?");
+        args.push(scheduled.to_string());
+    } else if edit_arg.clear_scheduled {
+        set_clauses.push("scheduled = NULL");
+    }
 
-    let mut query = sqlx::query::<sqlx::Sqlite>(&query_str);
-    for arg in args {
-        query = query.bind(arg);
+    if let Some(priority) = edit_arg.priority {
+        set_clauses.push("priority = ?");
+        args.push((priority as i64).to_string());
+    }
+
+    if let Some(recur) = edit_arg.recur {
+        let due = edit_arg.due.or(task.due.filter(|_| !edit_arg.clear_due));
+        set_clauses.push("recurrence = ?");
+        args.push(anchor_recurrence(recur, due).to_string());
+    } else if edit_arg.clear_recur {
+        set_clauses.push("recurrence = NULL");
     }
-    query.execute(db).await?;
+
+    if !set_clauses.is_empty() {
+        query_str.push_str(&set_clauses.join(", "));
+        query_str.push_str(" WHERE id = ? AND deleted_at IS NULL");
+        args.push(task.id.to_string());
+
+        let mut query = sqlx::query::<sqlx::Sqlite>(&query_str);
+        for arg in args {
+            query = query.bind(arg);
+        }
+        query.execute(db).await?;
+    }
+
+    tag_task(db, task.id, &edit_arg.tags).await?;
+    untag_task(db, task.id, &edit_arg.untags).await?;
     Ok(Message::ReloadTask)
 }
 
+/// Move a task and its subtasks to the trash
 async fn delete_task(db: &SqlitePool, delete_arg: TaskDeleteArg) -> Result<Message> {
-    sqlx::query("DELETE FROM tasks WHERE id = ?1")
-        .bind(delete_arg.id)
-        .execute(db)
+    let mut tx = db.begin().await?;
+    let task: Option<Task> =
+        sqlx::query_as("SELECT * FROM tasks WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(delete_arg.id)
+            .fetch_optional(&mut *tx)
+            .await?;
+    let Some(task) = task else {
+        return Ok(Message::ReloadTask);
+    };
+
+    if let Some(parent_id) = task.parent_id {
+        remove_sub_task(&mut tx, parent_id, task.id).await?;
+    }
+    sqlx::query(
+        "WITH RECURSIVE subtree(id) AS (
+            SELECT ?1
+            UNION
+            SELECT tasks.id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.id
+        )
+        UPDATE tasks
+        SET deleted_at = strftime('%Y-%m-%d %H:%M:%f', 'now')
+        WHERE id IN (SELECT id FROM subtree) AND deleted_at IS NULL",
+    )
+    .bind(task.id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(Message::ReloadTask)
+}
+
+/// Number of subtasks, at any depth, that go to the trash along with the task
+pub(crate) async fn count_sub_tasks(db: &SqlitePool, id: i64) -> Result<usize> {
+    let count: i64 = sqlx::query_scalar(
+        "WITH RECURSIVE subtree(id) AS (
+            SELECT ?1
+            UNION
+            SELECT tasks.id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.id
+            WHERE tasks.deleted_at IS NULL
+        )
+        SELECT COUNT(*) - 1 FROM subtree",
+    )
+    .bind(id)
+    .fetch_one(db)
+    .await?;
+    Ok(count as usize)
+}
+
+/// Move a task along with its subtasks under another parent or project
+///
+/// Without a parent the task becomes a top level task in the target project, otherwise the
+/// subtree follows the project of its new parent.
+async fn move_task(db: &SqlitePool, move_arg: TaskMoveArg) -> Result<Message> {
+    let mut tx = db.begin().await?;
+    let task = read_live_task(&mut *tx, move_arg.id).await?;
+
+    let (parent_id, project_id) = match move_arg.parent_id {
+        Some(parent_id) => {
+            let parent = read_live_task(&mut *tx, parent_id).await?;
+            if let Some(project_id) = move_arg.project_id
+                && project_id != parent.project_id
+            {
+                return Err(Error::custom(format!(
+                    "Task {parent_id} is not in project {project_id}"
+                )));
+            }
+
+            // The new parent cannot be the task itself or one of its subtasks
+            let is_cycle: bool = sqlx::query_scalar(
+                "WITH RECURSIVE subtree(id) AS (
+                    SELECT ?1
+                    UNION
+                    SELECT tasks.id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.id
+                )
+                SELECT EXISTS (SELECT 1 FROM subtree WHERE id = ?2)",
+            )
+            .bind(task.id)
+            .bind(parent_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            if is_cycle {
+                return Err(Error::custom(format!(
+                    "Cannot move task {} under its own subtask {parent_id}",
+                    task.id
+                )));
+            }
+            (Some(parent_id), parent.project_id)
+        }
+        None => {
+            let project_id = move_arg.project_id.unwrap_or(task.project_id);
+            check_live_project(&mut *tx, project_id).await?;
+            // A task keeps its parent when it stays in the same project, unless asked otherwise
+            let parent_id = task
+                .parent_id
+                .filter(|_| !move_arg.root && project_id == task.project_id);
+            (parent_id, project_id)
+        }
+    };
+
+    if parent_id != task.parent_id {
+        set_parent(&mut tx, &task, parent_id).await?;
+    }
+
+    sqlx::query(
+        "WITH RECURSIVE subtree(id) AS (
+            SELECT ?1
+            UNION
+            SELECT tasks.id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.id
+        )
+        UPDATE tasks SET project_id = ?2 WHERE id IN (SELECT id FROM subtree)",
+    )
+    .bind(task.id)
+    .bind(project_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(Message::ReloadTask)
+}
+
+/// Move a task under another parent, as its last subtask, keeping `sub_task_ids` in sync
+async fn set_parent(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    task: &Task,
+    parent_id: Option<i64>,
+) -> Result<()> {
+    if let Some(old_parent_id) = task.parent_id {
+        remove_sub_task(tx, old_parent_id, task.id).await?;
+    }
+    if let Some(parent_id) = parent_id {
+        sqlx::query(
+            "UPDATE tasks
+            SET sub_task_ids = json_insert(sub_task_ids,'$[#]',?1)
+            WHERE id = ?2",
+        )
+        .bind(task.id)
+        .bind(parent_id)
+        .execute(&mut **tx)
         .await?;
+    }
+    sqlx::query(
+        "UPDATE tasks
+        SET parent_id = ?1, position = (SELECT MAX(position) + 1 FROM tasks)
+        WHERE id = ?2",
+    )
+    .bind(parent_id)
+    .bind(task.id)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Drop a task from the `sub_task_ids` of its parent
+async fn remove_sub_task(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    parent_id: i64,
+    task_id: i64,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE tasks
+        SET sub_task_ids = (
+            SELECT json_group_array(value)
+            FROM json_each(sub_task_ids)
+            WHERE CAST(value AS INTEGER) != ?1
+        )
+        WHERE id = ?2",
+    )
+    .bind(task_id)
+    .bind(parent_id)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Reorder a task among its siblings, in the order the TUI shows them
+///
+/// Siblings are sorted by priority first, so a task only moves past siblings of the same
+/// priority.
+async fn reorder_task(db: &SqlitePool, reorder_arg: TaskReorderArg) -> Result<Message> {
+    let mut tx = db.begin().await?;
+    let task = read_live_task(&mut *tx, reorder_arg.id).await?;
+    let siblings: Vec<Task> = sqlx::query_as(
+        "SELECT * FROM tasks
+        WHERE project_id = ?1 AND parent_id IS ?2 AND deleted_at IS NULL
+        ORDER BY priority DESC, position, id",
+    )
+    .bind(task.project_id)
+    .bind(task.parent_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let index = siblings.iter().position(|t| t.id == task.id).unwrap_or_default();
+
+    match reorder_arg.direction {
+        ReorderDirection::Up | ReorderDirection::Down => {
+            let other = match reorder_arg.direction {
+                ReorderDirection::Up => index.checked_sub(1).and_then(|i| siblings.get(i)),
+                _ => siblings.get(index + 1),
+            };
+            if let Some(other) = other.filter(|o| o.priority == task.priority) {
+                for (id, position) in [(task.id, other.position), (other.id, task.position)] {
+                    sqlx::query("UPDATE tasks SET position = ?1 WHERE id = ?2")
+                        .bind(position)
+                        .bind(id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+        }
+        ReorderDirection::Indent => {
+            if let Some(parent) = index.checked_sub(1).and_then(|i| siblings.get(i)) {
+                set_parent(&mut tx, &task, Some(parent.id)).await?;
+            }
+        }
+        ReorderDirection::Outdent => {
+            if let Some(parent_id) = task.parent_id {
+                let parent: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = ?1")
+                    .bind(parent_id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+                set_parent(&mut tx, &task, parent.parent_id).await?;
+
+                // Make room right after the old parent
+                sqlx::query("UPDATE tasks SET position = position + 1 WHERE position > ?1")
+                    .bind(parent.position)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query("UPDATE tasks SET position = ?1 WHERE id = ?2")
+                    .bind(parent.position + 1)
+                    .bind(task.id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+    }
+    tx.commit().await?;
+
+    Ok(Message::ReloadTask)
+}
+
+/// Fold or unfold tasks, only tasks with subtasks are folded
+async fn fold_task(db: &SqlitePool, fold_arg: TaskFoldArg) -> Result<Message> {
+    sqlx::query(
+        "UPDATE tasks
+        SET folded = ?1
+        WHERE (id IN (SELECT value FROM json_each(?2)) OR project_id = ?3)
+          AND (NOT ?1 OR json_array_length(sub_task_ids) > 0)",
+    )
+    .bind(!fold_arg.unfold)
+    .bind(sqlx::types::Json(&fold_arg.ids))
+    .bind(fold_arg.project_id)
+    .execute(db)
+    .await?;
 
     Ok(Message::ReloadTask)
 }
 
 async fn done_task(db: &SqlitePool, edit_arg: TaskDoneArg) -> Result<Message> {
-    sqlx::query("UPDATE tasks SET done = true WHERE id = ?1")
-        .bind(edit_arg.id)
-        .execute(db)
-        .await?;
+    let mut tx = db.begin().await?;
+    let task = read_live_task(&mut *tx, edit_arg.id).await?;
+
+    sqlx::query(
+        "UPDATE tasks
+        SET done = true, completed = IIF(done, completed, date('now', 'localtime'))
+        WHERE id = ?1",
+    )
+    .bind(task.id)
+    .execute(&mut *tx)
+    .await?;
+
+    if !task.done
+        && let Some(recurrence) = &task.recurrence
+    {
+        let recurrence: Recurrence = recurrence.parse().map_err(Error::Custom)?;
+        spawn_next_occurrence(&mut tx, &task, &recurrence).await?;
+    }
+    tx.commit().await?;
 
     Ok(Message::ReloadTask)
 }
 
+/// Copy a completed recurring task and its subtasks with dates shifted to the next occurrence
+///
+/// The recurrence moves over to the copy, so completing the same task twice does not spawn twice.
+async fn spawn_next_occurrence(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    task: &Task,
+    recurrence: &Recurrence,
+) -> Result<()> {
+    let today = Local::now().date_naive();
+    let due = task.due.unwrap_or(today);
+    let mut next_due = recurrence.next_after(due);
+    while next_due <= today {
+        next_due = recurrence.next_after(next_due);
+    }
+    let shift = format!("{:+} days", (next_due - due).num_days());
+
+    // (task id to copy, parent id of the copy)
+    let mut queue = VecDeque::from([(task.id, task.parent_id)]);
+    while let Some((id, parent_id)) = queue.pop_front() {
+        let copy_id: i64 = sqlx::query_scalar(
+            "INSERT INTO tasks
+                (title, description, project_id, parent_id, due, scheduled, priority, recurrence,
+                 created)
+            SELECT title, description, project_id, ?2,
+                   COALESCE(date(due, ?3), ?4), date(scheduled, ?3), priority, recurrence,
+                   date('now', 'localtime')
+            FROM tasks
+            WHERE id = ?1
+            RETURNING id",
+        )
+        .bind(id)
+        .bind(parent_id)
+        .bind(&shift)
+        .bind((id == task.id).then_some(next_due))
+        .fetch_one(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO task_tags (task_id, tag_id)
+            SELECT ?2, tag_id FROM task_tags WHERE task_id = ?1",
+        )
+        .bind(id)
+        .bind(copy_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if let Some(parent_id) = parent_id {
+            sqlx::query(
+                "UPDATE tasks
+                SET sub_task_ids = json_insert(sub_task_ids,'$[#]',?1)
+                WHERE id = ?2",
+            )
+            .bind(copy_id)
+            .bind(parent_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        let sub_task_ids: Vec<i64> = sqlx::query_scalar(
+            "SELECT id FROM tasks WHERE parent_id = ?1 AND deleted_at IS NULL ORDER BY id",
+        )
+        .bind(id)
+        .fetch_all(&mut **tx)
+        .await?;
+        queue.extend(sub_task_ids.into_iter().map(|sub_id| (sub_id, Some(copy_id))));
+    }
+
+    sqlx::query("UPDATE tasks SET recurrence = NULL WHERE id = ?1")
+        .bind(task.id)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
+
 async fn undone_task(db: &SqlitePool, edit_arg: TaskDoneArg) -> Result<Message> {
-    sqlx::query("UPDATE tasks SET done = false WHERE id = ?1")
-        .bind(edit_arg.id)
+    let task = read_live_task(db, edit_arg.id).await?;
+    sqlx::query("UPDATE tasks SET done = false, completed = NULL WHERE id = ?1")
+        .bind(task.id)
         .execute(db)
         .await?;
 
@@ -163,6 +768,8 @@ mod tests {
     }
 
     use super::*;
+    use crate::objects::Priority;
+    use chrono::Datelike;
 
     #[tokio::test]
     async fn test_add_tasks() -> Result<()> {
@@ -174,6 +781,7 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: None,
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
@@ -184,6 +792,7 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: None,
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
@@ -208,6 +817,7 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: None,
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
@@ -220,6 +830,7 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: Some(parent_task_id),
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
@@ -251,6 +862,7 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: None,
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
         let subtask_title = "sub task";
@@ -260,11 +872,12 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: Some(parent_task_id),
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
         // -- Exec
-        let op = TaskOp::Delete(TaskDeleteArg { id: sub_task_id });
+        let op = TaskOp::Delete(TaskDeleteArg { id: sub_task_id, yes: true });
         delegate_task_op(&db, op).await?;
 
         // -- Check
@@ -294,23 +907,27 @@ mod tests {
                 description: None,
                 project_id: 1,
                 parent_id,
+                ..Default::default()
             });
             delegate_task_op(&db, op).await?;
         }
 
+        assert_eq!(count_sub_tasks(&db, parent_task_id).await?, 2);
+        assert_eq!(count_sub_tasks(&db, 3).await?, 0);
+
         // -- Exec
-        let op = TaskOp::Delete(TaskDeleteArg { id: parent_task_id });
+        let op = TaskOp::Delete(TaskDeleteArg { id: parent_task_id, yes: true });
         delegate_task_op(&db, op).await?;
+        assert_eq!(count_sub_tasks(&db, parent_task_id).await?, 0);
 
         // -- Check
-        for (id, _task_title) in tasks {
-            let task: Option<Task> = sqlx::query_as("SELECT * FROM tasks WHERE id = ?1")
-                .bind(id)
-                .fetch_optional(&db)
+        let deleted_at: Vec<Option<String>> =
+            sqlx::query_scalar("SELECT deleted_at FROM tasks ORDER BY id")
+                .fetch_all(&db)
                 .await?;
-
-            assert_eq!(None, task);
-        }
+        assert_eq!(deleted_at.len(), tasks.len());
+        assert!(deleted_at.iter().all(|d| d.is_some() && *d == deleted_at[0]));
+        assert!(read_task(&db, Default::default()).await?.is_empty());
         Ok(())
     }
 
@@ -324,6 +941,7 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: None,
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
@@ -332,8 +950,9 @@ mod tests {
         let list_arg = TaskListArg {
             project_id: Some(1),
             number: None,
+            ..Default::default()
         };
-        list_task(&db, list_arg, &mut stdout).await?;
+        list_task(&db, list_arg, OutputFormat::Plain, &mut stdout).await?;
 
         // -- Check
         assert!(stdout
@@ -352,6 +971,7 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: None,
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
@@ -362,6 +982,7 @@ mod tests {
             id: edited_task_id,
             title: Some(edited_task_title.to_string()),
             description: None,
+            ..Default::default()
         });
         delegate_task_op(&db, edit_arg).await?;
 
@@ -386,21 +1007,24 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: None,
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
         // -- Exec
         let task_id = 1;
-        let delete_arg = TaskOp::Delete(TaskDeleteArg { id: task_id });
+        let delete_arg = TaskOp::Delete(TaskDeleteArg { id: task_id, yes: true });
         delegate_task_op(&db, delete_arg).await?;
 
         // -- Check
-        let task: Option<Task> = sqlx::query_as("SELECT * FROM tasks WHERE title = ?1")
-            .bind(task_title)
-            .fetch_optional(&db)
-            .await?;
+        let deleted_at: Option<String> =
+            sqlx::query_scalar("SELECT deleted_at FROM tasks WHERE title = ?1")
+                .bind(task_title)
+                .fetch_one(&db)
+                .await?;
 
-        assert!(task.is_none());
+        assert!(deleted_at.is_some());
+        assert!(read_task(&db, Default::default()).await?.is_empty());
         Ok(())
     }
 
@@ -414,6 +1038,7 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: None,
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
@@ -422,6 +1047,8 @@ mod tests {
             .fetch_one(&db)
             .await?;
         assert!(!task.done);
+        assert_eq!(task.created, Some(Local::now().date_naive()));
+        assert_eq!(task.completed, None);
 
         // -- Exec
         let task_id = 1;
@@ -435,6 +1062,16 @@ mod tests {
             .await?;
 
         assert!(task.done);
+        assert_eq!(task.completed, Some(Local::now().date_naive()));
+
+        let undone_arg = TaskOp::UnDone(TaskDoneArg { id: task_id });
+        delegate_task_op(&db, undone_arg).await?;
+        let completed: Option<NaiveDate> =
+            sqlx::query_scalar("SELECT completed FROM tasks WHERE id = ?1")
+                .bind(task_id)
+                .fetch_one(&db)
+                .await?;
+        assert_eq!(completed, None);
         Ok(())
     }
 
@@ -450,6 +1087,7 @@ mod tests {
             description: None,
             project_id: 1,
             parent_id: None,
+            ..Default::default()
         });
         delegate_task_op(&db, op).await?;
 
@@ -472,6 +1110,540 @@ mod tests {
 
         Ok(())
     }
+    #[tokio::test]
+    async fn test_task_due_filters() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let today = chrono::Local::now().date_naive();
+        // (title, due)
+        let tasks = [
+            ("overdue task", Some(today - chrono::Days::new(2))),
+            ("due later task", Some(today + chrono::Days::new(5))),
+            ("no due task", None),
+        ];
+        for (title, due) in tasks {
+            let op = TaskOp::Add(TaskAddArg {
+                title: title.to_string(),
+                due,
+                ..Default::default()
+            });
+            delegate_task_op(&db, op).await?;
+        }
+
+        // -- Exec
+        let overdue = read_task(
+            &db,
+            TaskListArg {
+                overdue: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+        let due_before = read_task(
+            &db,
+            TaskListArg {
+                due_before: Some(today + chrono::Days::new(7)),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        // -- Check
+        let overdue = overdue.into_iter().map(|t| t.title).collect::<Vec<_>>();
+        assert_eq!(overdue, vec!["overdue task"]);
+        let due_before = due_before.into_iter().map(|t| t.title).collect::<Vec<_>>();
+        assert_eq!(due_before, vec!["overdue task", "due later task"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_edit_task_dates() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let due = chrono::NaiveDate::from_ymd_opt(2025, 7, 20).unwrap();
+        let op = TaskOp::Add(TaskAddArg {
+            title: "Test edit task dates".to_string(),
+            due: Some(due),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Exec
+        let scheduled = chrono::NaiveDate::from_ymd_opt(2025, 7, 18).unwrap();
+        let edit_arg = TaskOp::Edit(TaskEditArg {
+            id: 1,
+            scheduled: Some(scheduled),
+            clear_due: true,
+            ..Default::default()
+        });
+        delegate_task_op(&db, edit_arg).await?;
+
+        // -- Check
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(task.due, None);
+        assert_eq!(task.scheduled, Some(scheduled));
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_task_priority() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let op = TaskOp::Add(TaskAddArg {
+            title: "Test task priority".to_string(),
+            priority: Priority::High,
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Exec
+        let edit_arg = TaskOp::Edit(TaskEditArg {
+            id: 1,
+            priority: Some(Priority::Urgent),
+            ..Default::default()
+        });
+        delegate_task_op(&db, edit_arg).await?;
+
+        // -- Check
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(task.priority, Priority::Urgent);
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_task_tags() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        // (title, tags)
+        let tasks = [
+            ("fix login", vec!["bug", "urgent"]),
+            ("write docs", vec!["docs"]),
+        ];
+        for (title, tags) in tasks {
+            let op = TaskOp::Add(TaskAddArg {
+                title: title.to_string(),
+                tags: tags.into_iter().map(String::from).collect(),
+                ..Default::default()
+            });
+            delegate_task_op(&db, op).await?;
+        }
+
+        // -- Exec
+        let edit_arg = TaskOp::Edit(TaskEditArg {
+            id: 1,
+            tags: vec!["waiting-on".to_string()],
+            untags: vec!["urgent".to_string()],
+            ..Default::default()
+        });
+        delegate_task_op(&db, edit_arg).await?;
+        let list_arg = TaskListArg {
+            tags: vec!["bug".to_string()],
+            ..Default::default()
+        };
+        let tasks = read_task(&db, list_arg).await?;
+
+        // -- Check
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "fix login");
+        assert_eq!(tasks[0].tags.to_vec(), vec!["bug", "waiting-on"]);
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_done_recurring_task() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let today = chrono::Local::now().date_naive();
+        let due = today + chrono::Days::new(1);
+        let op = TaskOp::Add(TaskAddArg {
+            title: "water plants".to_string(),
+            due: Some(due),
+            recur: Some("every 3 days".parse()?),
+            tags: vec!["chore".to_string()],
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+        let op = TaskOp::Add(TaskAddArg {
+            title: "fill can".to_string(),
+            parent_id: Some(1),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Exec
+        delegate_task_op(&db, TaskOp::Done(TaskDoneArg { id: 1 })).await?;
+
+        // -- Check
+        let tasks = read_task(&db, TaskListArg::default()).await?;
+        let titles = tasks.iter().map(|t| t.title.as_str()).collect::<Vec<_>>();
+        assert_eq!(titles, vec!["water plants", "fill can", "water plants", "fill can"]);
+
+        let (done, next, next_sub) = (&tasks[0], &tasks[2], &tasks[3]);
+        assert!(done.done);
+        assert_eq!(done.recurrence, None);
+        assert!(!next.done);
+        assert_eq!(next.due, Some(due + chrono::Days::new(3)));
+        assert_eq!(next.recurrence.as_deref(), Some("FREQ=DAILY;INTERVAL=3"));
+        assert_eq!(next.tags.to_vec(), vec!["chore"]);
+        assert_eq!(next.sub_task_ids.to_vec(), vec![next_sub.id]);
+        assert_eq!(next_sub.parent_id, Some(next.id));
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_recurring_task_on_the_31st() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let year = chrono::Local::now().year() + 1;
+        let date = |month, day| NaiveDate::from_ymd_opt(year, month, day).ok_or("invalid date");
+        let op = TaskOp::Add(TaskAddArg {
+            title: "pay rent".to_string(),
+            due: Some(date(1, 31)?),
+            recur: Some("monthly".parse()?),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+        let op = TaskOp::Add(TaskAddArg {
+            title: "send report".to_string(),
+            due: Some(date(8, 31)?),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Exec
+        delegate_task_op(&db, TaskOp::Done(TaskDoneArg { id: 1 })).await?;
+        delegate_task_op(&db, TaskOp::Done(TaskDoneArg { id: 3 })).await?;
+        let op = TaskOp::Edit(TaskEditArg {
+            id: 2,
+            recur: Some("monthly".parse()?),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Check
+        let tasks = read_task(&db, TaskListArg::default()).await?;
+        let dues: Vec<(u32, u32)> = tasks
+            .iter()
+            .filter_map(|t| t.due)
+            .map(|d| (d.month(), d.day()))
+            .collect();
+        let february = date(3, 1)?.pred_opt().ok_or("invalid date")?.day();
+        assert_eq!(dues, vec![(1, 31), (8, 31), (2, february), (3, 31)]);
+        assert_eq!(tasks[1].recurrence.as_deref(), Some("FREQ=MONTHLY;BYMONTHDAY=31"));
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_search_tasks() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        // (title, description)
+        let tasks = [
+            ("Renew passport", Some("Book an appointment at the embassy")),
+            ("Buy groceries", Some("Milk, eggs and bread")),
+            ("Passport photos", None),
+        ];
+        for (title, description) in tasks {
+            let op = TaskOp::Add(TaskAddArg {
+                title: title.to_string(),
+                description: description.map(String::from),
+                ..Default::default()
+            });
+            delegate_task_op(&db, op).await?;
+        }
+        let edit_arg = TaskOp::Edit(TaskEditArg {
+            id: 2,
+            description: Some("Ask the embassy about visas".to_string()),
+            ..Default::default()
+        });
+        delegate_task_op(&db, edit_arg).await?;
+
+        // -- Exec
+        let mut stdout = Vec::new();
+        let search_arg = TaskSearchArg {
+            query: "embass".to_string(),
+            project_id: None,
+            number: None,
+        };
+        search_task(&db, search_arg, OutputFormat::Plain, ("[", "]"), &mut stdout).await?;
+        let list_arg = TaskListArg {
+            search: Some("passport".to_string()),
+            ..Default::default()
+        };
+        let tasks = read_task(&db, list_arg).await?;
+
+        // -- Check
+        let stdout = String::from_utf8(stdout)?;
+        assert!(stdout.contains("1. Renew passport\n    Book an appointment at the [embassy]"));
+        assert!(stdout.contains("2. Buy groceries\n    Ask the [embassy] about visas"));
+        let titles = tasks.iter().map(|t| t.title.as_str()).collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Renew passport", "Passport photos"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("  "), None);
+        assert_eq!(fts_query("foo-bar baz"), Some("\"foo-bar\"* \"baz\"*".to_string()));
+        assert_eq!(fts_query("say \"hi"), Some("\"say\"* \"\"\"hi\"*".to_string()));
+    }
+    #[tokio::test]
+    async fn test_list_tasks_json() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let op = TaskOp::Add(TaskAddArg {
+            title: "Test list tasks as json".to_string(),
+            description: Some("with every field".to_string()),
+            priority: Priority::Medium,
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Exec
+        let mut stdout = Vec::new();
+        list_task(&db, TaskListArg::default(), OutputFormat::Json, &mut stdout).await?;
+
+        // -- Check
+        let json: serde_json::Value = serde_json::from_slice(&stdout)?;
+        let task = &json[0];
+        assert_eq!(task["id"], 1);
+        assert_eq!(task["title"], "Test list tasks as json");
+        assert_eq!(task["description"], "with every field");
+        assert_eq!(task["done"], false);
+        assert_eq!(task["priority"], "medium");
+        assert_eq!(task["project_id"], 1);
+        assert_eq!(task["project"], "INBOX");
+        assert_eq!(task["sub_task_ids"], serde_json::json!([]));
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_edit_task_clear_description() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let op = TaskOp::Add(TaskAddArg {
+            title: "Test clear description".to_string(),
+            description: Some("soon to be gone".to_string()),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Exec
+        let edit_arg = TaskOp::Edit(TaskEditArg {
+            id: 1,
+            description: Some(String::new()),
+            ..Default::default()
+        });
+        delegate_task_op(&db, edit_arg).await?;
+
+        // -- Check
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(task.description, None);
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_move_task() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        sqlx::query("INSERT INTO projects (id, name) VALUES (2, 'Work')")
+            .execute(&db)
+            .await?;
+        // 1 -> 2 -> 3, and 4 on its own
+        let tasks = [("one", None), ("two", Some(1)), ("three", Some(2)), ("four", None)];
+        for (title, parent_id) in tasks {
+            let op = TaskOp::Add(TaskAddArg {
+                title: title.to_string(),
+                parent_id,
+                ..Default::default()
+            });
+            delegate_task_op(&db, op).await?;
+        }
+        let read = |id: i64| {
+            sqlx::query_as::<_, Task>("SELECT * FROM tasks WHERE id = ?1")
+                .bind(id)
+                .fetch_one(&db)
+        };
+
+        // -- Exec
+        let op = TaskOp::Move(TaskMoveArg {
+            id: 2,
+            parent_id: Some(4),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+        let op = TaskOp::Move(TaskMoveArg {
+            id: 4,
+            parent_id: Some(3),
+            ..Default::default()
+        });
+        let cycle = delegate_task_op(&db, op).await;
+        let op = TaskOp::Move(TaskMoveArg {
+            id: 4,
+            project_id: Some(2),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+
+        // -- Check
+        assert!(cycle.is_err());
+        assert!(read(1).await?.sub_task_ids.is_empty());
+        assert_eq!(read(4).await?.sub_task_ids.to_vec(), vec![2]);
+        assert_eq!(read(2).await?.parent_id, Some(4));
+        for id in [2, 3, 4] {
+            assert_eq!(read(id).await?.project_id, 2);
+        }
+        assert_eq!(read(1).await?.project_id, 1);
+
+        // Trashed tasks and projects can neither move nor take tasks
+        sqlx::query("INSERT INTO projects (id, name, deleted_at) VALUES (3, 'Old', 'now')")
+            .execute(&db)
+            .await?;
+        delegate_task_op(&db, TaskOp::Delete(TaskDeleteArg { id: 1, yes: true })).await?;
+        let moves = [(1, None, None), (4, Some(1), None), (4, None, Some(3))];
+        for (id, parent_id, project_id) in moves {
+            let op = TaskOp::Move(TaskMoveArg {
+                id,
+                parent_id,
+                project_id,
+                ..Default::default()
+            });
+            assert!(delegate_task_op(&db, op).await.is_err());
+        }
+        assert_eq!(read(4).await?.project_id, 2);
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_trashed_task() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        sqlx::query("INSERT INTO projects (id, name, deleted_at) VALUES (2, 'Old', 'now')")
+            .execute(&db)
+            .await?;
+        let op = TaskOp::Add(TaskAddArg {
+            title: "trashed".to_string(),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+        delegate_task_op(&db, TaskOp::Delete(TaskDeleteArg { id: 1, yes: true })).await?;
+
+        // -- Exec
+        let ops = [
+            TaskOp::Add(TaskAddArg {
+                title: "child".to_string(),
+                project_id: 2,
+                ..Default::default()
+            }),
+            TaskOp::Add(TaskAddArg {
+                title: "sub".to_string(),
+                parent_id: Some(1),
+                ..Default::default()
+            }),
+            TaskOp::Edit(TaskEditArg {
+                id: 1,
+                title: Some("renamed".to_string()),
+                ..Default::default()
+            }),
+            TaskOp::Edit(TaskEditArg {
+                id: 1,
+                tags: vec!["foo".to_string()],
+                ..Default::default()
+            }),
+            TaskOp::Done(TaskDoneArg { id: 1 }),
+            TaskOp::UnDone(TaskDoneArg { id: 1 }),
+        ];
+        let mut results = Vec::new();
+        for op in ops {
+            results.push(delegate_task_op(&db, op).await);
+        }
+
+        // -- Check
+        assert!(results.iter().all(|result| result.is_err()));
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(task.title, "trashed");
+        assert!(!task.done);
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks").fetch_one(&db).await?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_reorder_task() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        for title in ["one", "two", "three"] {
+            let op = TaskOp::Add(TaskAddArg {
+                title: title.to_string(),
+                ..Default::default()
+            });
+            delegate_task_op(&db, op).await?;
+        }
+        let reorder = |id: i64, direction: ReorderDirection| {
+            delegate_task_op(&db, TaskOp::Reorder(TaskReorderArg { id, direction }))
+        };
+        let root_ids = || {
+            sqlx::query_scalar::<_, i64>(
+                "SELECT id FROM tasks WHERE parent_id IS NULL ORDER BY position",
+            )
+            .fetch_all(&db)
+        };
+
+        // -- Exec & Check
+        reorder(3, ReorderDirection::Up).await?;
+        assert_eq!(root_ids().await?, vec![1, 3, 2]);
+
+        reorder(2, ReorderDirection::Down).await?;
+        assert_eq!(root_ids().await?, vec![1, 3, 2]);
+
+        reorder(3, ReorderDirection::Indent).await?;
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(root_ids().await?, vec![1, 2]);
+        assert_eq!(task.sub_task_ids.to_vec(), vec![3]);
+
+        reorder(3, ReorderDirection::Outdent).await?;
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(root_ids().await?, vec![1, 3, 2]);
+        assert!(task.sub_task_ids.is_empty());
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_fold_task() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        for (title, parent_id) in [("parent", None), ("child", Some(1)), ("other", None)] {
+            let op = TaskOp::Add(TaskAddArg {
+                title: title.to_string(),
+                parent_id,
+                ..Default::default()
+            });
+            delegate_task_op(&db, op).await?;
+        }
+        let folded_ids = || {
+            sqlx::query_scalar::<_, i64>("SELECT id FROM tasks WHERE folded ORDER BY id")
+                .fetch_all(&db)
+        };
+
+        // -- Exec & Check
+        let op = TaskOp::Fold(TaskFoldArg {
+            project_id: Some(1),
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+        assert_eq!(folded_ids().await?, vec![1]);
+
+        let op = TaskOp::Fold(TaskFoldArg {
+            ids: vec![1],
+            unfold: true,
+            ..Default::default()
+        });
+        delegate_task_op(&db, op).await?;
+        assert!(folded_ids().await?.is_empty());
+        Ok(())
+    }
 }
 
 // endregion: --- Tests
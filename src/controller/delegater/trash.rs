@@ -0,0 +1,315 @@
+use crate::{
+    app::Message,
+    cli::{OutputFormat, TrashOp, TrashPurgeArg, TrashRestoreArg},
+    controller::{
+        delegater::journal::{journal_commit, journal_mark},
+        output::write_records,
+    },
+    objects::{Task, TrashItem},
+    Error, Result,
+};
+use sqlx::SqlitePool;
+
+pub(crate) async fn delegate_trash_op(db: &SqlitePool, op: TrashOp) -> Result<Message> {
+    let mark = journal_mark(db).await?;
+    let message = match op {
+        TrashOp::List => list_trash(db, OutputFormat::Plain, std::io::stdout()).await,
+        TrashOp::Restore(restore_arg) if restore_arg.project => {
+            restore_project(db, restore_arg).await
+        }
+        TrashOp::Restore(restore_arg) => restore_task(db, restore_arg).await,
+        TrashOp::Purge(purge_arg) => purge_trash(db, purge_arg).await,
+    };
+    journal_commit(db, mark).await?;
+
+    message
+}
+
+/// Deleted projects and tasks, most recently deleted first
+///
+/// Tasks deleted along with their project or parent are left out, restoring those restores
+/// them too.
+pub async fn read_trash(db: &SqlitePool) -> Result<Vec<TrashItem>> {
+    sqlx::query_as(
+        "SELECT 'project' AS kind, id, name, deleted_at
+        FROM projects
+        WHERE deleted_at IS NOT NULL
+        UNION ALL
+        SELECT 'task' AS kind, tasks.id, tasks.title AS name, tasks.deleted_at
+        FROM tasks
+        LEFT JOIN projects ON tasks.project_id = projects.id
+        LEFT JOIN tasks AS parent ON tasks.parent_id = parent.id
+        WHERE tasks.deleted_at IS NOT NULL
+          AND projects.deleted_at IS NOT tasks.deleted_at
+          AND parent.deleted_at IS NOT tasks.deleted_at
+        ORDER BY deleted_at DESC, kind, id",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(Into::into)
+}
+
+pub(crate) async fn list_trash(
+    db: &SqlitePool,
+    format: OutputFormat,
+    writer: impl std::io::Write,
+) -> Result<Message> {
+    let trash = read_trash(db).await?;
+    write_records(writer, format, &trash)?;
+
+    Ok(Message::Noop)
+}
+
+/// Restore a task along with the subtasks deleted at the same time
+///
+/// A task whose parent is still in the trash is restored as a top level task.
+async fn restore_task(db: &SqlitePool, restore_arg: TrashRestoreArg) -> Result<Message> {
+    let mut tx = db.begin().await?;
+    let deleted_at: String = sqlx::query_scalar(
+        "SELECT deleted_at FROM tasks WHERE id = ?1 AND deleted_at IS NOT NULL",
+    )
+    .bind(restore_arg.id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| Error::custom(format!("Task {} is not in the trash", restore_arg.id)))?;
+    let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = ?1")
+        .bind(restore_arg.id)
+        .fetch_one(&mut *tx)
+        .await?;
+    let project_deleted: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM projects WHERE id = ?1 AND deleted_at IS NOT NULL)",
+    )
+    .bind(task.project_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if project_deleted {
+        return Err(Error::custom(format!(
+            "Project {} is in the trash, restore it first",
+            task.project_id
+        )));
+    }
+
+    sqlx::query(
+        "WITH RECURSIVE subtree(id) AS (
+            SELECT ?1
+            UNION
+            SELECT tasks.id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.id
+        )
+        UPDATE tasks
+        SET deleted_at = NULL
+        WHERE id IN (SELECT id FROM subtree) AND deleted_at = ?2",
+    )
+    .bind(task.id)
+    .bind(deleted_at)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(parent_id) = task.parent_id {
+        let parent_restored = sqlx::query(
+            "UPDATE tasks
+            SET sub_task_ids = json_insert(sub_task_ids,'$[#]',?1)
+            WHERE id = ?2 AND deleted_at IS NULL",
+        )
+        .bind(task.id)
+        .bind(parent_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            > 0;
+        if !parent_restored {
+            sqlx::query("UPDATE tasks SET parent_id = NULL WHERE id = ?1")
+                .bind(task.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+    tx.commit().await?;
+
+    Ok(Message::ReloadTrash)
+}
+
+/// Restore a project along with the tasks deleted with it
+async fn restore_project(db: &SqlitePool, restore_arg: TrashRestoreArg) -> Result<Message> {
+    let mut tx = db.begin().await?;
+    let deleted_at: String = sqlx::query_scalar(
+        "SELECT deleted_at FROM projects WHERE id = ?1 AND deleted_at IS NOT NULL",
+    )
+    .bind(restore_arg.id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| Error::custom(format!("Project {} is not in the trash", restore_arg.id)))?;
+
+    sqlx::query("UPDATE projects SET deleted_at = NULL WHERE id = ?1")
+        .bind(restore_arg.id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE tasks SET deleted_at = NULL WHERE project_id = ?1 AND deleted_at = ?2")
+        .bind(restore_arg.id)
+        .bind(deleted_at)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Message::ReloadTrash)
+}
+
+/// Permanently delete tasks and projects in the trash
+async fn purge_trash(db: &SqlitePool, purge_arg: TrashPurgeArg) -> Result<Message> {
+    let cutoff = format!("-{} days", purge_arg.older_than.unwrap_or_default());
+    let mut tx = db.begin().await?;
+    // Subtasks deleted with their parent go with it through tasks_before_delete_cascade,
+    // and tasks of a project through its foreign key
+    sqlx::query(
+        "DELETE FROM tasks
+        WHERE deleted_at <= strftime('%Y-%m-%d %H:%M:%f', 'now', ?1)",
+    )
+    .bind(&cutoff)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "DELETE FROM projects
+        WHERE deleted_at <= strftime('%Y-%m-%d %H:%M:%f', 'now', ?1)",
+    )
+    .bind(&cutoff)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(Message::ReloadTrash)
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+    use crate::{
+        cli::{ProjectAddArg, ProjectDeleteArg, ProjectOp, TaskAddArg, TaskDeleteArg, TaskOp},
+        controller::delegater::{delegate_project_op, delegate_task_op, read_task},
+    };
+
+    async fn init_db() -> Result<sqlx::SqlitePool> {
+        let db = sqlx::sqlite::SqlitePool::connect("sqlite::memory:").await?;
+        // create table
+        sqlx::migrate!("./migrations").run(&db).await?;
+        Ok(db)
+    }
+
+    async fn add_tasks(db: &SqlitePool, project_id: i64) -> Result<()> {
+        // (parent_id, title)
+        let tasks = [(None, "parent"), (Some(1), "child"), (Some(2), "grand child")];
+        for (parent_id, title) in tasks {
+            let op = TaskOp::Add(TaskAddArg {
+                title: title.to_string(),
+                project_id,
+                parent_id,
+                ..Default::default()
+            });
+            delegate_task_op(db, op).await?;
+        }
+        Ok(())
+    }
+
+    async fn task_ids(db: &SqlitePool) -> Result<Vec<i64>> {
+        let tasks = read_task(db, Default::default()).await?;
+        Ok(tasks.into_iter().map(|t| t.id).collect())
+    }
+
+    #[tokio::test]
+    async fn test_restore_task() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        add_tasks(&db, 1).await?;
+        delegate_task_op(&db, TaskOp::Delete(TaskDeleteArg { id: 3, yes: true })).await?;
+        delegate_task_op(&db, TaskOp::Delete(TaskDeleteArg { id: 1, yes: true })).await?;
+        let trash = read_trash(&db).await?;
+
+        // -- Exec
+        let op = TrashOp::Restore(TrashRestoreArg {
+            id: 3,
+            project: false,
+        });
+        delegate_trash_op(&db, op).await?;
+
+        // -- Check
+        assert_eq!(trash.iter().map(|t| t.id).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(task_ids(&db).await?, vec![3]);
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = 3")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(task.parent_id, None);
+
+        let op = TrashOp::Restore(TrashRestoreArg {
+            id: 1,
+            project: false,
+        });
+        delegate_trash_op(&db, op).await?;
+        assert_eq!(task_ids(&db).await?, vec![1, 2, 3]);
+        let task: Task = sqlx::query_as("SELECT * FROM tasks WHERE id = 1")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(task.sub_task_ids.to_vec(), vec![2]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_restore_project() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        let op = ProjectOp::Add(ProjectAddArg {
+            name: "Work".to_string(),
+        });
+        delegate_project_op(&db, op).await?;
+        add_tasks(&db, 2).await?;
+        delegate_project_op(&db, ProjectOp::Delete(ProjectDeleteArg { id: 2, yes: true })).await?;
+
+        // -- Exec
+        let task_restore = TrashOp::Restore(TrashRestoreArg {
+            id: 1,
+            project: false,
+        });
+        let task_restore = delegate_trash_op(&db, task_restore).await;
+        let trash = read_trash(&db).await?;
+        let op = TrashOp::Restore(TrashRestoreArg {
+            id: 2,
+            project: true,
+        });
+        delegate_trash_op(&db, op).await?;
+
+        // -- Check
+        assert!(task_restore.is_err());
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].kind, "project");
+        assert_eq!(task_ids(&db).await?, vec![1, 2, 3]);
+        assert!(read_trash(&db).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_purge_trash() -> Result<()> {
+        // -- Setup & Fixtures
+        let db = init_db().await?;
+        add_tasks(&db, 1).await?;
+        delegate_task_op(&db, TaskOp::Delete(TaskDeleteArg { id: 1, yes: true })).await?;
+
+        // -- Exec
+        let op = TrashOp::Purge(TrashPurgeArg {
+            older_than: Some(1),
+        });
+        delegate_trash_op(&db, op).await?;
+        let kept = read_trash(&db).await?.len();
+        delegate_trash_op(&db, TrashOp::Purge(TrashPurgeArg::default())).await?;
+
+        // -- Check
+        assert_eq!(kept, 1);
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks")
+            .fetch_one(&db)
+            .await?;
+        assert_eq!(count, 0);
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
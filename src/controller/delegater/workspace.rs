@@ -0,0 +1,185 @@
+use crate::{
+    app::Message,
+    cli::{OutputFormat, WorkspaceArg, WorkspaceOp},
+    constants::DEFAULT_WORKSPACE,
+    controller::{
+        init::{
+            current_workspace, current_workspace_file, data_dir, init_db, validate_name,
+            workspace_path,
+        },
+        output::write_records,
+    },
+    objects::Workspace,
+    Error, Result,
+};
+use std::path::Path;
+
+pub(crate) async fn delegate_workspace_op(
+    op: WorkspaceOp,
+    format: OutputFormat,
+) -> Result<Message> {
+    let data_dir = data_dir()?;
+    match op {
+        WorkspaceOp::List => list_workspace(&data_dir, format, std::io::stdout()),
+        WorkspaceOp::Create(workspace_arg) => create_workspace(&data_dir, workspace_arg).await,
+        WorkspaceOp::Switch(workspace_arg) => switch_workspace(&data_dir, workspace_arg),
+        WorkspaceOp::Remove(workspace_arg) => remove_workspace(&data_dir, workspace_arg),
+    }
+}
+
+/// The default workspace followed by the named ones, by name
+fn read_workspace(data_dir: &Path) -> Result<Vec<Workspace>> {
+    let mut names = Vec::new();
+    match std::fs::read_dir(data_dir.join("workspaces")) {
+        Ok(entries) => {
+            for entry in entries {
+                let path = entry?.path();
+                if path.extension().is_some_and(|ext| ext == "db")
+                    && let Some(name) = path.file_stem().and_then(|stem| stem.to_str())
+                {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    names.sort();
+    names.insert(0, DEFAULT_WORKSPACE.to_string());
+
+    let current = current_workspace(data_dir)?;
+    let workspaces = names
+        .into_iter()
+        .map(|name| Workspace {
+            path: workspace_path(data_dir, &name).display().to_string(),
+            current: name == current,
+            name,
+        })
+        .collect();
+    Ok(workspaces)
+}
+
+fn list_workspace(
+    data_dir: &Path,
+    format: OutputFormat,
+    writer: impl std::io::Write,
+) -> Result<Message> {
+    let workspaces = read_workspace(data_dir)?;
+    write_records(writer, format, &workspaces)?;
+
+    Ok(Message::Noop)
+}
+
+fn existing_path(data_dir: &Path, name: &str) -> Result<std::path::PathBuf> {
+    validate_name(name)?;
+    let db_path = workspace_path(data_dir, name);
+    if name != DEFAULT_WORKSPACE && !db_path.exists() {
+        return Err(Error::custom(format!("Workspace {name} does not exist")));
+    }
+    Ok(db_path)
+}
+
+async fn create_workspace(data_dir: &Path, workspace_arg: WorkspaceArg) -> Result<Message> {
+    validate_name(&workspace_arg.name)?;
+    let db_path = workspace_path(data_dir, &workspace_arg.name);
+    if db_path.exists() {
+        return Err(Error::custom(format!(
+            "Workspace {} already exists",
+            workspace_arg.name
+        )));
+    }
+
+    init_db(&db_path).await?.close().await;
+    Ok(Message::Noop)
+}
+
+fn switch_workspace(data_dir: &Path, workspace_arg: WorkspaceArg) -> Result<Message> {
+    existing_path(data_dir, &workspace_arg.name)?;
+
+    std::fs::create_dir_all(data_dir)?;
+    std::fs::write(current_workspace_file(data_dir), &workspace_arg.name)?;
+    Ok(Message::Noop)
+}
+
+fn remove_workspace(data_dir: &Path, workspace_arg: WorkspaceArg) -> Result<Message> {
+    let db_path = existing_path(data_dir, &workspace_arg.name)?;
+    if workspace_arg.name == DEFAULT_WORKSPACE {
+        return Err(Error::custom("The default workspace cannot be removed"));
+    }
+    if workspace_arg.name == current_workspace(data_dir)? {
+        return Err(Error::custom(format!(
+            "Workspace {} is the current one, switch to another first",
+            workspace_arg.name
+        )));
+    }
+
+    std::fs::remove_file(&db_path)?;
+    // SQLite may leave its journal files next to the database
+    for suffix in ["-wal", "-shm", "-journal"] {
+        let mut sidecar = db_path.clone().into_os_string();
+        sidecar.push(suffix);
+        match std::fs::remove_file(sidecar) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+    }
+    Ok(Message::Noop)
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    fn workspace_arg(name: &str) -> WorkspaceArg {
+        WorkspaceArg {
+            name: name.to_string(),
+        }
+    }
+
+    fn names(data_dir: &Path) -> Result<Vec<(String, bool)>> {
+        let workspaces = read_workspace(data_dir)?;
+        Ok(workspaces.into_iter().map(|w| (w.name, w.current)).collect())
+    }
+
+    #[tokio::test]
+    async fn test_workspace_lifecycle() -> Result<()> {
+        // -- Setup & Fixtures
+        let data_dir = std::env::temp_dir().join(format!("twodo-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+
+        // -- Exec & Check
+        create_workspace(&data_dir, workspace_arg("work")).await?;
+        create_workspace(&data_dir, workspace_arg("home")).await?;
+        assert!(create_workspace(&data_dir, workspace_arg("work")).await.is_err());
+        assert!(create_workspace(&data_dir, workspace_arg("../work")).await.is_err());
+        assert_eq!(
+            names(&data_dir)?,
+            vec![
+                ("default".to_string(), true),
+                ("home".to_string(), false),
+                ("work".to_string(), false),
+            ]
+        );
+
+        switch_workspace(&data_dir, workspace_arg("work"))?;
+        assert!(switch_workspace(&data_dir, workspace_arg("play")).is_err());
+        assert_eq!(current_workspace(&data_dir)?, "work");
+        assert!(remove_workspace(&data_dir, workspace_arg("work")).is_err());
+        assert!(remove_workspace(&data_dir, workspace_arg("default")).is_err());
+
+        remove_workspace(&data_dir, workspace_arg("home"))?;
+        assert_eq!(
+            names(&data_dir)?,
+            vec![("default".to_string(), false), ("work".to_string(), true)]
+        );
+
+        std::fs::remove_dir_all(&data_dir)?;
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
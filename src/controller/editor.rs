@@ -0,0 +1,135 @@
+use crate::{Error, Result};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::atomic::{AtomicU32, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Open `path` in `$VISUAL`, `$EDITOR` or `vi`, and wait for it to exit
+pub(crate) fn open_editor(path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // The variables may hold arguments too, like `code --wait`
+    let mut words = editor.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| Error::custom("The editor command is empty"))?;
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|err| Error::custom(format!("Failed to run {editor}: {err}")))?;
+    if !status.success() {
+        return Err(Error::custom(format!("{editor} exited with {status}")));
+    }
+    Ok(())
+}
+
+/// Edit a task in the editor, the title on the first line and the description below it
+///
+/// Returns the new title and description, an empty description clears it.
+pub(crate) fn edit_task_text(title: &str, description: &str) -> Result<(String, String)> {
+    let (path, mut file) = create_temp_file("twodo-task")?;
+    let written = file.write_all(task_text(title, description).as_bytes());
+    drop(file);
+    let text = written
+        .map_err(Error::from)
+        .and_then(|_| open_editor(&path))
+        .and_then(|_| Ok(std::fs::read_to_string(&path)?));
+    let _ = std::fs::remove_file(&path);
+    parse_task_text(&text?)
+}
+
+/// Create a file of a new name in the temp dir, only readable by the user
+///
+/// The file must not exist yet, so a symlink planted in a shared temp dir is never followed.
+fn create_temp_file(prefix: &str) -> Result<(PathBuf, File)> {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    for _ in 0..100 {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.subsec_nanos());
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let name = format!("{prefix}-{}-{nanos:x}-{count}.md", std::process::id());
+        let path = std::env::temp_dir().join(name);
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Err(Error::custom("Failed to create a temporary file for the editor"))
+}
+
+fn task_text(title: &str, description: &str) -> String {
+    if description.is_empty() {
+        format!("{title}\n")
+    } else {
+        format!("{title}\n\n{description}\n")
+    }
+}
+
+fn parse_task_text(text: &str) -> Result<(String, String)> {
+    let (title, description) = text.split_once('\n').unwrap_or((text, ""));
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(Error::custom("The title is empty, the task is left unchanged"));
+    }
+    let description = description.trim_start_matches(['\r', '\n']).trim_end();
+    Ok((title.to_string(), description.to_string()))
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    #[test]
+    fn test_parse_task_text() -> Result<()> {
+        // -- Setup & Fixtures
+        let description = "- [ ] milk\n  - oat\n- [ ] bread";
+        let text = task_text("Groceries", description);
+
+        // -- Exec
+        let parsed = parse_task_text(&text)?;
+
+        // -- Check
+        assert_eq!(parsed, ("Groceries".to_string(), description.to_string()));
+        assert_eq!(
+            parse_task_text("  Call mom  \n")?,
+            ("Call mom".to_string(), String::new())
+        );
+        assert!(parse_task_text("\n\nsome notes").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_temp_file() -> Result<()> {
+        // -- Exec
+        let (first, _) = create_temp_file("twodo-test")?;
+        let (second, _) = create_temp_file("twodo-test")?;
+
+        // -- Check
+        assert_ne!(first, second);
+        for path in [first, second] {
+            // A name that is taken is never opened again
+            assert!(OpenOptions::new().write(true).create_new(true).open(&path).is_err());
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -1,30 +1,93 @@
-use crate::Result;
-use sqlx::{SqlitePool, migrate::MigrateDatabase};
+use crate::{constants::DEFAULT_WORKSPACE, Error, Result};
+use sqlx::{sqlite::SqliteConnectOptions, SqlitePool};
+use std::path::{Path, PathBuf};
 
-pub async fn init_db() -> Result<SqlitePool> {
-    // get db path
-    let db_path = std::env::home_dir()
-        .map(|h| h.join(".local/share/twodo/twodo.db"))
-        .expect("Failed to get xdg base directory");
+/// Directory holding the databases, `$XDG_DATA_HOME/twodo` or `~/.local/share/twodo`
+pub(crate) fn data_dir() -> Result<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::home_dir().map(|h| h.join(".local/share")))
+        .ok_or_else(|| {
+            Error::custom("Failed to find the home directory, set XDG_DATA_HOME or TWODO_DB")
+        })?;
+    Ok(data_home.join("twodo"))
+}
+
+/// Workspace names end up in file names, so they are kept to a safe set of characters
+pub(crate) fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(Error::custom(format!(
+            "Invalid workspace name {name:?}, use letters, digits, '-' and '_'"
+        )));
+    }
+    Ok(())
+}
+
+/// Database file of the workspace `name`
+pub(crate) fn workspace_path(data_dir: &Path, name: &str) -> PathBuf {
+    if name == DEFAULT_WORKSPACE {
+        return data_dir.join("twodo.db");
+    }
+    data_dir.join("workspaces").join(format!("{name}.db"))
+}
+
+/// File naming the workspace used when none is given
+pub(crate) fn current_workspace_file(data_dir: &Path) -> PathBuf {
+    data_dir.join("workspace")
+}
+
+pub(crate) fn current_workspace(data_dir: &Path) -> Result<String> {
+    match std::fs::read_to_string(current_workspace_file(data_dir)) {
+        Ok(name) if !name.trim().is_empty() => Ok(name.trim().to_string()),
+        Ok(_) => Ok(DEFAULT_WORKSPACE.to_string()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(DEFAULT_WORKSPACE.to_string()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Database to open, in order of `--db`, `--workspace`, `TWODO_DB` and the current workspace
+pub(crate) fn resolve_db_path(db: Option<PathBuf>, workspace: Option<&str>) -> Result<PathBuf> {
+    if let Some(db) = db {
+        return Ok(db);
+    }
+    if workspace.is_none()
+        && let Some(db) = std::env::var_os("TWODO_DB").filter(|db| !db.is_empty())
+    {
+        return Ok(PathBuf::from(db));
+    }
+
+    let data_dir = data_dir()?;
+    let name = match workspace {
+        Some(name) => name.to_string(),
+        None => current_workspace(&data_dir)?,
+    };
+    validate_name(&name)?;
+    let db_path = workspace_path(&data_dir, &name);
+    // Opening a misspelt workspace would silently create an empty one
+    if name != DEFAULT_WORKSPACE && !db_path.exists() {
+        return Err(Error::custom(format!(
+            "Workspace {name} does not exist, create it with `twodo workspace create {name}`"
+        )));
+    }
+    Ok(db_path)
+}
 
+pub async fn init_db(db_path: &Path) -> Result<SqlitePool> {
     // create db path
-    std::fs::create_dir_all(
-        db_path
-            .parent()
-            .expect("Failed to get parent of the twodo db path"),
-    )?;
-
-    let db_url = db_path
-        .to_str()
-        .expect("Failed to convert twodo_db_path to str");
-
-    // create database if not existing
-    if !sqlx::Sqlite::database_exists(db_url).await? {
-        sqlx::Sqlite::create_database(db_url).await?;
+    if let Some(parent) = db_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
     }
 
-    // connect to database
-    let db = sqlx::SqlitePool::connect(db_url).await?;
+    // connect to database, creating it if not existing
+    let options = SqliteConnectOptions::new()
+        .filename(db_path)
+        .create_if_missing(true);
+    let db = SqlitePool::connect_with(options).await?;
     sqlx::migrate!("./migrations").run(&db).await?;
     Ok(db)
 }
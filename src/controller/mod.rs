@@ -1,5 +1,7 @@
 // region:    --- Modules
 mod init;
+mod output;
+pub(crate) mod editor;
 
 // -- Public Modules
 pub mod delegater;
@@ -0,0 +1,323 @@
+use crate::{
+    cli::OutputFormat,
+    config::config,
+    keymap::Binding,
+    objects::{Project, Tag, Task, TrashItem, Workspace},
+    Result,
+};
+use serde::Serialize;
+use std::io::Write;
+
+/// An item printed by a list command
+pub(crate) trait Record: Serialize {
+    /// Column names for table, csv and tsv output
+    const HEADERS: &'static [&'static str];
+
+    /// Column values, in the same order as `HEADERS`
+    fn fields(&self) -> Vec<String>;
+
+    /// Line used for plain output
+    fn plain(&self) -> String;
+}
+
+/// A task along with the name of its project
+#[derive(Serialize)]
+pub(crate) struct TaskRecord<'a> {
+    #[serde(flatten)]
+    pub task: &'a Task,
+    pub project: &'a str,
+}
+
+impl Record for TaskRecord<'_> {
+    const HEADERS: &'static [&'static str] = &[
+        "id",
+        "title",
+        "done",
+        "priority",
+        "due",
+        "scheduled",
+        "project",
+        "parent_id",
+        "tags",
+        "recurrence",
+        "description",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        let task = self.task;
+        vec![
+            task.id.to_string(),
+            task.title.clone(),
+            task.done.to_string(),
+            format!("{:?}", task.priority).to_lowercase(),
+            optional(task.due),
+            optional(task.scheduled),
+            self.project.to_string(),
+            optional(task.parent_id),
+            task.tags.join(","),
+            optional(task.recurrence.as_ref()),
+            optional(task.description.as_ref()),
+        ]
+    }
+
+    fn plain(&self) -> String {
+        match self.task.due {
+            Some(due) => format!(
+                "{}. {} (due {})",
+                self.task.id,
+                self.task.title,
+                config().format_date(due)
+            ),
+            None => format!("{}. {}", self.task.id, self.task.title),
+        }
+    }
+}
+
+impl Record for Project {
+    const HEADERS: &'static [&'static str] = &["id", "name"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![self.id.to_string(), self.name.clone()]
+    }
+
+    fn plain(&self) -> String {
+        format!("{}. {}", self.id, self.name)
+    }
+}
+
+impl Record for Tag {
+    const HEADERS: &'static [&'static str] = &["id", "name"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![self.id.to_string(), self.name.clone()]
+    }
+
+    fn plain(&self) -> String {
+        format!("{}. {}", self.id, self.name)
+    }
+}
+
+impl Record for TrashItem {
+    const HEADERS: &'static [&'static str] = &["kind", "id", "name", "deleted_at"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.kind.clone(),
+            self.id.to_string(),
+            self.name.clone(),
+            self.deleted_at.clone(),
+        ]
+    }
+
+    fn plain(&self) -> String {
+        format!(
+            "{} {}. {} (deleted {})",
+            self.kind, self.id, self.name, self.deleted_at
+        )
+    }
+}
+
+impl Record for Workspace {
+    const HEADERS: &'static [&'static str] = &["name", "path", "current"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![self.name.clone(), self.path.clone(), self.current.to_string()]
+    }
+
+    fn plain(&self) -> String {
+        let marker = if self.current { "*" } else { " " };
+        format!("{marker} {}", self.name)
+    }
+}
+
+impl Record for Binding {
+    const HEADERS: &'static [&'static str] = &["context", "keys", "action"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.context.to_string(),
+            self.keys.clone(),
+            self.action.to_string(),
+        ]
+    }
+
+    fn plain(&self) -> String {
+        format!("{:<12} {:<10} {}", self.context, self.keys, self.action)
+    }
+}
+
+fn optional(value: Option<impl ToString>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+pub(crate) fn write_records<R: Record>(
+    mut writer: impl Write,
+    format: OutputFormat,
+    records: &[R],
+) -> Result<()> {
+    match format {
+        OutputFormat::Plain => {
+            for record in records {
+                writeln!(writer, "{}", record.plain())?;
+            }
+        }
+        OutputFormat::Table => write_table(writer, records)?,
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, records)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for record in records {
+                serde_json::to_writer(&mut writer, record)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Csv => write_separated(writer, records, ",", escape_csv)?,
+        OutputFormat::Tsv => write_separated(writer, records, "\t", escape_tsv)?,
+    }
+    Ok(())
+}
+
+fn write_table<R: Record>(mut writer: impl Write, records: &[R]) -> Result<()> {
+    // Multi-line values would break the rows apart
+    let rows = records
+        .iter()
+        .map(|r| r.fields().into_iter().map(|f| f.replace('\n', " ")).collect())
+        .collect::<Vec<Vec<_>>>();
+
+    let mut widths = R::HEADERS.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows.iter() {
+        for (width, field) in widths.iter_mut().zip(row) {
+            *width = (*width).max(field.chars().count());
+        }
+    }
+
+    let write_row = |writer: &mut dyn Write, row: &[&str]| -> std::io::Result<()> {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(field, &width)| format!("{field:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(writer, "{}", line.trim_end())
+    };
+
+    write_row(&mut writer, R::HEADERS)?;
+    let rule = widths.iter().map(|&w| "-".repeat(w)).collect::<Vec<_>>();
+    write_row(&mut writer, &rule.iter().map(String::as_str).collect::<Vec<_>>())?;
+    for row in rows.iter() {
+        write_row(&mut writer, &row.iter().map(String::as_str).collect::<Vec<_>>())?;
+    }
+    Ok(())
+}
+
+fn write_separated<R: Record>(
+    mut writer: impl Write,
+    records: &[R],
+    separator: &str,
+    escape: fn(&str) -> String,
+) -> Result<()> {
+    writeln!(writer, "{}", R::HEADERS.join(separator))?;
+    for record in records {
+        let fields = record.fields().iter().map(|f| escape(f)).collect::<Vec<_>>();
+        writeln!(writer, "{}", fields.join(separator))?;
+    }
+    Ok(())
+}
+
+/// Quote fields as described in RFC 4180
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// TSV has no quoting, so tabs and newlines are escaped with backslashes
+fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    fn projects() -> Vec<Project> {
+        vec![
+            Project {
+                id: 1,
+                name: "INBOX".to_string(),
+            },
+            Project {
+                id: 12,
+                name: "Home, \"sweet\" home".to_string(),
+            },
+        ]
+    }
+
+    fn render(format: OutputFormat) -> Result<String> {
+        let mut out = Vec::new();
+        write_records(&mut out, format, &projects())?;
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn test_write_records() -> Result<()> {
+        assert_eq!(render(OutputFormat::Plain)?, "1. INBOX\n12. Home, \"sweet\" home\n");
+        assert_eq!(
+            render(OutputFormat::Table)?,
+            "id  name\n--  ------------------\n1   INBOX\n12  Home, \"sweet\" home\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Csv)?,
+            "id,name\n1,INBOX\n12,\"Home, \"\"sweet\"\" home\"\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Tsv)?,
+            "id\tname\n1\tINBOX\n12\tHome, \"sweet\" home\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Ndjson)?,
+            "{\"id\":1,\"name\":\"INBOX\"}\n{\"id\":12,\"name\":\"Home, \\\"sweet\\\" home\"}\n"
+        );
+        let json: serde_json::Value = serde_json::from_str(&render(OutputFormat::Json)?)?;
+        assert_eq!(json[1]["name"], "Home, \"sweet\" home");
+        Ok(())
+    }
+
+    #[test]
+    fn test_task_record_json() -> Result<()> {
+        let task = Task {
+            id: 3,
+            title: "Write report".to_string(),
+            project_id: 12,
+            tags: sqlx::types::Json(vec!["work".to_string()]),
+            ..Default::default()
+        };
+        let record = TaskRecord {
+            task: &task,
+            project: "Home",
+        };
+
+        let json = serde_json::to_value(&record)?;
+
+        assert_eq!(json["title"], "Write report");
+        assert_eq!(json["project"], "Home");
+        assert_eq!(json["project_id"], 12);
+        assert_eq!(json["priority"], "none");
+        assert_eq!(json["tags"], serde_json::json!(["work"]));
+        assert_eq!(json["due"], serde_json::Value::Null);
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -22,6 +22,9 @@ pub enum Error {
 
     #[from]
     Migrate(sqlx::migrate::MigrateError),
+
+    #[from]
+    Json(serde_json::Error),
 }
 
 // region:    --- Custom
@@ -34,8 +37,45 @@ impl Error {
     pub fn custom(val: impl Into<String>) -> Self {
         Self::Custom(val.into())
     }
+
+    /// Whether the TUI has to stop, other errors are shown in the status bar
+    ///
+    /// Only failing IO and a corrupt or unreadable database are fatal.
+    pub fn is_fatal(&self) -> bool {
+        match self {
+            Self::Io(_) | Self::Migrate(_) => true,
+            Self::Sql(sqlx::Error::Io(_) | sqlx::Error::PoolClosed) => true,
+            Self::Sql(sqlx::Error::Database(err)) => {
+                // SQLite extended codes keep the primary code in the low byte
+                let code = err.code().and_then(|code| code.parse::<i32>().ok());
+                matches!(
+                    code.map(|code| code & 0xff),
+                    Some(SQLITE_IOERR | SQLITE_CORRUPT | SQLITE_FULL | SQLITE_NOTADB)
+                )
+            }
+            _ => false,
+        }
+    }
+
+    /// Short description for the status bar
+    pub fn message(&self) -> String {
+        match self {
+            Self::Custom(message) => message.clone(),
+            Self::MissingProjectId => "No project selected".to_string(),
+            Self::MissingTaskId => "No task selected".to_string(),
+            Self::Io(err) => err.to_string(),
+            Self::Sql(err) => err.to_string(),
+            Self::Migrate(err) => err.to_string(),
+            Self::Json(err) => err.to_string(),
+        }
+    }
 }
 
+const SQLITE_IOERR: i32 = 10;
+const SQLITE_CORRUPT: i32 = 11;
+const SQLITE_FULL: i32 = 13;
+const SQLITE_NOTADB: i32 = 26;
+
 // endregion: --- Custom
 
 // region:    --- Error Boilerplate
@@ -49,3 +89,21 @@ impl Display for Error {
 impl std::error::Error for Error {}
 
 // endregion: --- Error Boilerplate
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_fatal() {
+        assert!(!Error::MissingTaskId.is_fatal());
+        assert!(!Error::custom("Nothing to restore").is_fatal());
+        assert!(!Error::Sql(sqlx::Error::RowNotFound).is_fatal());
+        assert!(Error::Io(std::io::Error::other("disk")).is_fatal());
+        assert!(Error::Sql(sqlx::Error::PoolClosed).is_fatal());
+    }
+}
+
+// endregion: --- Tests
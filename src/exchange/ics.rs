@@ -0,0 +1,339 @@
+use crate::{
+    constants::PROJECT_INBOX_ID,
+    exchange::{format_timestamp, parse_timestamp, ExportTask, ImportTask, TIMESTAMP},
+    objects::{Priority, Recurrence},
+    Result,
+};
+use chrono::{NaiveDate, Utc};
+use std::{collections::HashMap, io::Write};
+
+/// Property holding the project name, which calendars have no field for
+const PROJECT: &str = "X-TWODO-PROJECT";
+
+/// Longest line in octets before it is folded, see RFC 5545 section 3.1
+const LINE_LIMIT: usize = 75;
+
+/// Tasks of the VTODO components of an iCalendar file, see RFC 5545
+///
+/// ```text
+/// BEGIN:VTODO
+/// UID:0b5e1c3a-1111-4000-8000-000000000002
+/// SUMMARY:Book flights
+/// STATUS:COMPLETED
+/// RELATED-TO:0b5e1c3a-1111-4000-8000-000000000001
+/// END:VTODO
+/// ```
+///
+/// Besides SUMMARY, DESCRIPTION, STATUS, UID and RELATED-TO for the parent, the DUE, DTSTART,
+/// PRIORITY, RRULE, CATEGORIES, CREATED and COMPLETED properties are read. Cancelled to-dos are
+/// skipped.
+pub fn parse(content: &str) -> Vec<ImportTask> {
+    let mut tasks = Vec::new();
+    let mut task: Option<ImportTask> = None;
+    let mut cancelled = false;
+
+    for line in unfold(content) {
+        let Some((name, params, value)) = parse_property(&line) else {
+            continue;
+        };
+        match (name.as_str(), task.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VTODO") => {
+                task = Some(ImportTask::default());
+                cancelled = false;
+            }
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VTODO") => {
+                tasks.extend(task.take().filter(|_| !cancelled));
+            }
+            ("UID", Some(task)) => {
+                task.key = Some(value.to_string());
+                task.uuid = Some(value.to_string());
+            }
+            ("SUMMARY", Some(task)) => task.title = unescape(value),
+            ("DESCRIPTION", Some(task)) => task.description = Some(unescape(value)),
+            ("STATUS", Some(task)) => {
+                task.done = value.eq_ignore_ascii_case("COMPLETED");
+                cancelled = value.eq_ignore_ascii_case("CANCELLED");
+            }
+            // Other relations, like siblings, do not fit the subtask tree
+            ("RELATED-TO", Some(task))
+                if params.as_deref().is_none_or(|p| p.contains("RELTYPE=PARENT")) =>
+            {
+                task.parent = Some(value.to_string());
+            }
+            ("DUE", Some(task)) => task.due = parse_date(value),
+            ("DTSTART", Some(task)) => task.scheduled = parse_date(value),
+            ("PRIORITY", Some(task)) => task.priority = parse_priority(value),
+            ("RRULE", Some(task)) => {
+                task.recurrence = value.parse::<Recurrence>().ok().map(|r| r.to_string());
+            }
+            ("CATEGORIES", Some(task)) => task.tags.extend(split_list(value)),
+            ("CREATED", Some(task)) => task.created = parse_date(value),
+            ("COMPLETED", Some(task)) => {
+                task.done = true;
+                task.completed = parse_date(value);
+            }
+            (PROJECT, Some(task)) => task.project = Some(unescape(value)),
+            _ => {}
+        }
+    }
+    tasks
+}
+
+/// Join the lines folded onto the next ones, which start with a space or a tab
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Upper case name, parameters and value of a content line like `DUE;VALUE=DATE:20250801`
+fn parse_property(line: &str) -> Option<(String, Option<String>, &str)> {
+    // Parameter values may hold a colon between quotes
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(index, c)| {
+        match c {
+            '"' => quoted = !quoted,
+            ':' if !quoted => return Some(index),
+            _ => {}
+        }
+        None
+    })?;
+    let (name, value) = (&line[..colon], &line[colon + 1..]);
+    let (name, params) = match name.split_once(';') {
+        Some((name, params)) => (name, Some(params.to_uppercase())),
+        None => (name, None),
+    };
+    Some((name.trim().to_uppercase(), params, value))
+}
+
+/// Local day of a DATE, a UTC DATE-TIME, or the day of a local one
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    parse_timestamp(value).or_else(|| NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok())
+}
+
+fn parse_priority(value: &str) -> Priority {
+    match value.trim().parse::<u8>() {
+        Ok(1) => Priority::Urgent,
+        Ok(2..=4) => Priority::High,
+        Ok(5) => Priority::Medium,
+        Ok(6..=9) => Priority::Low,
+        _ => Priority::None,
+    }
+}
+
+fn format_priority(priority: Priority) -> Option<u8> {
+    match priority {
+        Priority::None => None,
+        Priority::Low => Some(7),
+        Priority::Medium => Some(5),
+        Priority::High => Some(3),
+        Priority::Urgent => Some(1),
+    }
+}
+
+/// Items of a comma separated list of TEXT values
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // An escaped comma does not end the item
+            '\\' => {
+                item.push(c);
+                item.extend(chars.next());
+            }
+            ',' => items.push(std::mem::take(&mut item)),
+            c => item.push(c),
+        }
+    }
+    items.push(item);
+    items
+        .iter()
+        .map(|item| unescape(item.trim()))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n' | 'N') => text.push('\n'),
+                Some(c) => text.push(c),
+                None => {}
+            },
+            c => text.push(c),
+        }
+    }
+    text
+}
+
+fn escape(text: &str) -> String {
+    let mut value = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                value.push('\\');
+                value.push(c);
+            }
+            '\n' => value.push_str("\\n"),
+            '\r' => {}
+            c => value.push(c),
+        }
+    }
+    value
+}
+
+/// Write the tasks as a calendar of VTODO components, the UID of a task never changes
+pub fn write(mut writer: impl Write, tasks: &[ExportTask]) -> Result<()> {
+    let uuids: HashMap<i64, &str> = tasks
+        .iter()
+        .map(|export| (export.task.id, export.task.uuid.as_str()))
+        .collect();
+    let stamp = Utc::now().format(TIMESTAMP).to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//twodo//twodo//EN".to_string(),
+    ];
+    for export in tasks {
+        lines.extend(format_task(export, &uuids, &stamp));
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    for line in lines {
+        write!(writer, "{}\r\n", fold(&line))?;
+    }
+    Ok(())
+}
+
+/// Content lines of a task, `uuids` are those of the parents and `stamp` the time of the export
+pub fn format_task(export: &ExportTask, uuids: &HashMap<i64, &str>, stamp: &str) -> Vec<String> {
+    let task = export.task;
+    let mut lines = vec![
+        "BEGIN:VTODO".to_string(),
+        format!("UID:{}", task.uuid),
+        format!("DTSTAMP:{stamp}"),
+        format!("SUMMARY:{}", escape(&task.title)),
+    ];
+    if let Some(description) = task.description.as_deref().filter(|d| !d.is_empty()) {
+        lines.push(format!("DESCRIPTION:{}", escape(description)));
+    }
+    let status = if task.done { "COMPLETED" } else { "NEEDS-ACTION" };
+    lines.push(format!("STATUS:{status}"));
+    if let Some(parent) = task.parent_id.and_then(|id| uuids.get(&id)) {
+        lines.push(format!("RELATED-TO;RELTYPE=PARENT:{parent}"));
+    }
+
+    // A to-do can not start after it is due
+    let scheduled = task.scheduled.filter(|s| task.due.is_none_or(|due| *s <= due));
+    lines.extend(scheduled.map(|d| format!("DTSTART;VALUE=DATE:{}", d.format("%Y%m%d"))));
+    lines.extend(task.due.map(|d| format!("DUE;VALUE=DATE:{}", d.format("%Y%m%d"))));
+    lines.extend(format_priority(task.priority).map(|p| format!("PRIORITY:{p}")));
+    lines.extend(task.recurrence.as_deref().map(|r| format!("RRULE:{r}")));
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|tag| escape(tag)).collect();
+        lines.push(format!("CATEGORIES:{}", tags.join(",")));
+    }
+    lines.extend(task.created.map(|d| format!("CREATED:{}", format_timestamp(d))));
+    let completed = task.completed.filter(|_| task.done);
+    lines.extend(completed.map(|d| format!("COMPLETED:{}", format_timestamp(d))));
+    if task.project_id != PROJECT_INBOX_ID {
+        lines.push(format!("{PROJECT}:{}", escape(export.project)));
+    }
+    lines.push("END:VTODO".to_string());
+    lines
+}
+
+/// Split a line into lines of at most [`LINE_LIMIT`] octets, never inside a character
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > LINE_LIMIT {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        // -- Setup & Fixtures
+        let content = "BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+SUMMARY:Not a task\r
+END:VEVENT\r
+BEGIN:VTODO\r
+UID:a1\r
+SUMMARY:Call mom\\, dad\r
+DESCRIPTION:Ask about\\nthe dates\\; and a very long line that calendars fold o\r
+ nto the next one\r
+DUE;TZID=\"Europe/Berlin\":20250801T180000\r
+PRIORITY:2\r
+CATEGORIES:phone,family\r
+RRULE:FREQ=WEEKLY;INTERVAL=2\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:b2\r
+SUMMARY:Find the number\r
+RELATED-TO:a1\r
+COMPLETED:20250730T100000Z\r
+END:VTODO\r
+BEGIN:VTODO\r
+SUMMARY:Dropped\r
+STATUS:CANCELLED\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+        // -- Exec
+        let tasks = parse(content);
+
+        // -- Check
+        assert_eq!(tasks.len(), 2);
+        let (call, find) = (&tasks[0], &tasks[1]);
+        assert_eq!(call.title, "Call mom, dad");
+        assert_eq!(
+            call.description.as_deref(),
+            Some("Ask about\nthe dates; and a very long line that calendars fold onto the next one")
+        );
+        assert_eq!(call.due, NaiveDate::from_ymd_opt(2025, 8, 1));
+        assert_eq!(call.priority, Priority::High);
+        assert_eq!(call.tags, vec!["phone", "family"]);
+        assert_eq!(call.recurrence.as_deref(), Some("FREQ=WEEKLY;INTERVAL=2"));
+        assert_eq!(find.parent.as_deref(), Some("a1"));
+        assert!(find.done && find.completed.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fold() {
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+        let folded = fold(&line);
+        assert!(folded.split("\r\n").all(|part| part.len() <= LINE_LIMIT));
+        assert_eq!(unfold(&folded).concat(), line);
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,261 @@
+use crate::{
+    app::reorder_tasks,
+    exchange::{ExportTask, ImportTask},
+    objects::Task,
+    Result,
+};
+use std::{collections::BTreeMap, io::Write};
+
+/// Tasks of GitHub flavoured Markdown checklists, a heading names the project of the tasks below
+///
+/// ```text
+/// # Travel
+///
+/// - [ ] Plan the trip
+///
+///   Book the flights
+///   and the hotel
+///
+///   - [x] Ask for days off
+/// ```
+///
+/// Nested items are subtasks, and the text indented below an item is its description. Items
+/// before the first heading go to the inbox, other lines are left out. A backslash in front of a
+/// description line keeps it from reading as a heading or an item.
+pub fn parse(content: &str) -> Vec<ImportTask> {
+    let mut tasks: Vec<ImportTask> = Vec::new();
+    let mut project = None;
+    // (indent, key) of the items the next one may be nested in
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    // Indent of the text of the last item, which its description lines start at
+    let mut text_indent = None;
+
+    for line in content.lines() {
+        // Only ASCII indent, so that slicing it off lands on a char boundary
+        let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let trimmed = line.trim();
+        // Subtasks are nested in the description, headings are not
+        let in_description = text_indent.is_some_and(|start| indent >= start);
+        if !in_description && let Some(heading) = parse_heading(trimmed) {
+            project = Some(heading.to_string());
+            parents.clear();
+            text_indent = None;
+        } else if let Some((done, title)) = parse_item(trimmed) {
+            while parents.last().is_some_and(|(parent_indent, _)| *parent_indent >= indent) {
+                parents.pop();
+            }
+            let key = tasks.len().to_string();
+            tasks.push(ImportTask {
+                key: Some(key.clone()),
+                parent: parents.last().map(|(_, key)| key.clone()),
+                title: title.to_string(),
+                done,
+                project: project.clone(),
+                ..Default::default()
+            });
+            parents.push((indent, key));
+            text_indent = Some(indent + 2);
+        } else if let (Some(task), Some(start)) = (tasks.last_mut(), text_indent) {
+            if trimmed.is_empty() || indent >= start {
+                let description = task.description.get_or_insert_with(String::new);
+                if !trimmed.is_empty() {
+                    description.push_str(&unescape_line(&line[start..]));
+                }
+                description.push('\n');
+            } else {
+                // Text that is not indented ends the list
+                parents.clear();
+                text_indent = None;
+            }
+        }
+    }
+
+    for task in tasks.iter_mut() {
+        task.description = task
+            .description
+            .take()
+            .map(|d| d.trim_matches('\n').to_string())
+            .filter(|d| !d.is_empty());
+    }
+    tasks
+}
+
+/// Text of a `#` heading
+fn parse_heading(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    if text.len() == line.len() || !text.starts_with(' ') {
+        return None;
+    }
+    Some(text.trim()).filter(|text| !text.is_empty())
+}
+
+/// Whether a `- [ ]` item is checked, and its text
+fn parse_item(line: &str) -> Option<(bool, &str)> {
+    let line = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))?;
+    let done = match line.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    Some((done, line[3..].trim()))
+}
+
+/// Escape a description line that would read as a heading or an item
+fn escape_line(line: &str) -> String {
+    let text = line.trim_start();
+    let indent = &line[..line.len() - text.len()];
+    let trimmed = text.trim_end();
+    if parse_heading(trimmed).is_some() || parse_item(trimmed).is_some() || text.starts_with('\\') {
+        format!("{indent}\\{text}")
+    } else {
+        line.to_string()
+    }
+}
+
+/// Drop the backslash [`escape_line`] adds
+fn unescape_line(line: &str) -> String {
+    let text = line.trim_start();
+    let indent = &line[..line.len() - text.len()];
+    match text.strip_prefix('\\') {
+        Some(text) => format!("{indent}{text}"),
+        None => line.to_string(),
+    }
+}
+
+/// Write the tasks of each project under a heading, subtasks nested in their parent
+pub fn write(mut writer: impl Write, tasks: &[ExportTask]) -> Result<()> {
+    let mut projects: BTreeMap<i64, (&str, Vec<Task>)> = BTreeMap::new();
+    for export in tasks {
+        let (_, tasks) = projects
+            .entry(export.task.project_id)
+            .or_insert_with(|| (export.project, Vec::new()));
+        tasks.push(export.task.clone());
+    }
+
+    for (index, (name, tasks)) in projects.into_values().enumerate() {
+        if index > 0 {
+            writeln!(writer)?;
+        }
+        writeln!(writer, "# {name}")?;
+        writeln!(writer)?;
+        let (tasks, depths) = reorder_tasks(tasks);
+        for (task, depth) in tasks.iter().zip(depths) {
+            write_task(&mut writer, task, depth)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_task(mut writer: impl Write, task: &Task, depth: usize) -> Result<()> {
+    let indent = "  ".repeat(depth);
+    let check = if task.done { 'x' } else { ' ' };
+    writeln!(writer, "{indent}- [{check}] {}", task.title)?;
+
+    // A blank line keeps the description from joining the title
+    if let Some(description) = task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        writeln!(writer)?;
+        for line in description.lines() {
+            match line.trim_end() {
+                "" => writeln!(writer)?,
+                line => writeln!(writer, "{indent}  {}", escape_line(line))?,
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        // -- Setup & Fixtures
+        let content = "\
+Some notes
+- [ ] Inbox task
+## Travel
+* [ ] Plan the trip
+
+  Book the flights
+
+  and the hotel
+
+    - [x] Ask for days off
+    - [ ] Pack
+      - [X] Buy a bag
+- [ ] Rent a car
+- not a task
+Trailing notes
+- [ ] Wide
+　indented with an ideographic space
+";
+
+        // -- Exec
+        let tasks = parse(content);
+
+        // -- Check
+        let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Inbox task",
+                "Plan the trip",
+                "Ask for days off",
+                "Pack",
+                "Buy a bag",
+                "Rent a car",
+                "Wide"
+            ]
+        );
+        let parents: Vec<Option<&str>> = tasks.iter().map(|t| t.parent.as_deref()).collect();
+        assert_eq!(parents, [None, None, Some("1"), Some("1"), Some("3"), None, None]);
+        assert_eq!(tasks[0].project, None);
+        assert_eq!(tasks[5].project.as_deref(), Some("Travel"));
+        assert_eq!(
+            tasks[1].description.as_deref(),
+            Some("Book the flights\n\nand the hotel")
+        );
+        assert!(tasks[2].done && tasks[4].done && !tasks[3].done);
+        assert_eq!(tasks[5].description, None);
+        assert_eq!(tasks[6].description, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_description_round_trip() -> Result<()> {
+        // -- Setup & Fixtures
+        let task = Task {
+            id: 1,
+            title: "Write the notes".to_string(),
+            description: Some("# Notes\n- [ ] not a task\n  * [x] nor this\n\\path".to_string()),
+            project_id: 2,
+            ..Default::default()
+        };
+        let export = ExportTask {
+            task: &task,
+            project: "Docs",
+        };
+
+        // -- Exec
+        let mut content = Vec::new();
+        write(&mut content, &[export])?;
+        let tasks = parse(&String::from_utf8(content)?);
+
+        // -- Check
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].project.as_deref(), Some("Docs"));
+        assert_eq!(tasks[0].description, task.description);
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,99 @@
+// region:    --- Modules
+pub mod ics;
+pub mod markdown;
+pub mod taskwarrior;
+pub mod todotxt;
+
+// endregion: --- Modules
+
+use crate::objects::{Priority, Task};
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::HashSet;
+
+/// UTC timestamp of Taskwarrior and iCalendar, like `20250801T120000Z`
+pub const TIMESTAMP: &str = "%Y%m%dT%H%M%SZ";
+
+/// A task read from another format, before it is added to the database
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ImportTask {
+    /// Id of the task in the file, which its subtasks refer to
+    pub key: Option<String>,
+    /// Key of the parent task
+    pub parent: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub done: bool,
+    /// Name of the project, which is created when missing
+    pub project: Option<String>,
+    pub due: Option<NaiveDate>,
+    pub scheduled: Option<NaiveDate>,
+    pub priority: Priority,
+    /// Recurrence rule in RRULE form
+    pub recurrence: Option<String>,
+    pub tags: Vec<String>,
+    pub created: Option<NaiveDate>,
+    pub completed: Option<NaiveDate>,
+    /// Tasks with the UUID of a task in twodo are not imported again
+    pub uuid: Option<String>,
+    /// Attributes to keep in [`Task::extra`]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// A task to export along with the name of its project
+#[derive(Debug, Clone, Copy)]
+pub struct ExportTask<'a> {
+    pub task: &'a Task,
+    pub project: &'a str,
+}
+
+/// Order tasks so that parents come before their subtasks, keeping the order otherwise
+///
+/// Tasks caught in a cycle of parents lose their parent.
+pub fn parents_first(tasks: Vec<ImportTask>) -> Vec<ImportTask> {
+    let keys: HashSet<String> = tasks.iter().filter_map(|t| t.key.clone()).collect();
+    let mut placed = HashSet::new();
+    let mut ordered = Vec::with_capacity(tasks.len());
+    let mut pending = tasks;
+
+    while !pending.is_empty() {
+        let count = pending.len();
+        let mut waiting = Vec::new();
+        for task in pending {
+            let ready = task
+                .parent
+                .as_ref()
+                .is_none_or(|parent| !keys.contains(parent) || placed.contains(parent));
+            if ready {
+                placed.extend(task.key.clone());
+                ordered.push(task);
+            } else {
+                waiting.push(task);
+            }
+        }
+        if waiting.len() == count {
+            ordered.extend(waiting.into_iter().map(|task| ImportTask {
+                parent: None,
+                ..task
+            }));
+            break;
+        }
+        pending = waiting;
+    }
+    ordered
+}
+
+/// Local day of a [`TIMESTAMP`]
+pub fn parse_timestamp(value: &str) -> Option<NaiveDate> {
+    let utc = NaiveDateTime::parse_from_str(value, TIMESTAMP).ok()?.and_utc();
+    Some(utc.with_timezone(&Local).date_naive())
+}
+
+/// [`TIMESTAMP`] of the local midnight of `date`
+pub fn format_timestamp(date: NaiveDate) -> String {
+    let midnight = date.and_time(NaiveTime::MIN);
+    let utc = midnight
+        .and_local_timezone(Local)
+        .earliest()
+        .map_or(midnight, |time| time.naive_utc());
+    utc.format(TIMESTAMP).to_string()
+}
@@ -0,0 +1,449 @@
+use crate::{
+    constants::PROJECT_INBOX_ID,
+    exchange::{format_timestamp, parse_timestamp, ExportTask, ImportTask, TIMESTAMP},
+    objects::{Frequency, Priority, Recurrence},
+    Error, Result,
+};
+use chrono::Utc;
+use serde_json::{Map, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+};
+
+/// Attributes Taskwarrior works out on every export
+const COMPUTED: &[&str] = &["id", "urgency"];
+
+/// Attribute marking urgent tasks, Taskwarrior has no priority above `H`
+const URGENT: &str = "twodo_urgent";
+
+/// Attribute holding a description that the annotations do not give back, like one with blank lines
+const DESCRIPTION: &str = "twodo_description";
+
+/// Tasks of the JSON array `task export` writes
+///
+/// ```text
+/// [
+/// {"uuid":"…","description":"Plan the trip","status":"pending","project":"Travel",
+///  "annotations":[{"entry":"20250730T120000Z","description":"Book the flights"}]}
+/// ]
+/// ```
+///
+/// The description is the title and the annotations make up the description, one per line. A task
+/// becomes the parent of the tasks it depends on, unless they already have one. Deleted tasks and
+/// the instances of recurring tasks are skipped, the recurring task itself is imported.
+///
+/// The other attributes, and the exact timestamps twodo only keeps the day of, go to
+/// [`ImportTask::extra`] so that an export gives them back.
+pub fn parse(content: &str) -> Result<Vec<ImportTask>> {
+    // Older versions write one object per line instead of an array
+    let mut objects = Vec::new();
+    for value in serde_json::Deserializer::from_str(content).into_iter::<Value>() {
+        match value? {
+            Value::Array(values) => objects.extend(values),
+            value => objects.push(value),
+        }
+    }
+    let mut objects = objects
+        .into_iter()
+        .map(|value| match value {
+            Value::Object(object) => Ok(object),
+            _ => Err(Error::custom("Expected a JSON object for each task")),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    objects.retain(|object| {
+        object.get("status").and_then(Value::as_str) != Some("deleted")
+            && !object.contains_key("imask")
+    });
+
+    // A dependency can only have one parent, the others stay dependencies
+    let uuids: HashSet<String> = objects.iter().filter_map(|o| text(o, "uuid")).collect();
+    let mut parents: HashMap<String, String> = HashMap::new();
+    let mut leftovers = Vec::with_capacity(objects.len());
+    for object in objects.iter_mut() {
+        let uuid = text(object, "uuid");
+        let mut leftover = Vec::new();
+        for dependency in depends(object.remove("depends")) {
+            let fits = uuids.contains(&dependency)
+                && uuid.as_ref() != Some(&dependency)
+                && !parents.contains_key(&dependency);
+            match &uuid {
+                Some(uuid) if fits => {
+                    parents.insert(dependency, uuid.clone());
+                }
+                _ => leftover.push(dependency),
+            }
+        }
+        leftovers.push(leftover);
+    }
+
+    let tasks = objects
+        .into_iter()
+        .zip(leftovers)
+        .map(|(object, leftover)| {
+            let parent = text(&object, "uuid").and_then(|uuid| parents.remove(&uuid));
+            parse_task(object, parent, leftover)
+        })
+        .collect();
+    Ok(tasks)
+}
+
+fn parse_task(
+    mut object: Map<String, Value>,
+    parent: Option<String>,
+    depends: Vec<String>,
+) -> ImportTask {
+    for key in COMPUTED {
+        object.remove(*key);
+    }
+    let uuid = take_text(&mut object, "uuid");
+    let tags = match object.remove("tags") {
+        Some(Value::Array(tags)) => tags
+            .into_iter()
+            .filter_map(|tag| tag.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let mut description = annotations(&object).join("\n");
+    // Unless the annotations were changed in Taskwarrior since
+    if let Some(Value::String(exact)) = object.remove(DESCRIPTION)
+        && annotation_lines(&exact) == annotations(&object)
+    {
+        description = exact;
+    }
+    let mut priority = text(&object, "priority").as_deref().and_then(parse_priority);
+    // Unless the priority was changed in Taskwarrior since
+    if object.remove(URGENT).is_some() && priority == Some(Priority::High) {
+        priority = Some(Priority::Urgent);
+    }
+    if !depends.is_empty() {
+        object.insert("depends".to_string(), depends.into());
+    }
+
+    ImportTask {
+        key: uuid.clone(),
+        parent,
+        title: take_text(&mut object, "description").unwrap_or_default(),
+        description: Some(description).filter(|d| !d.is_empty()),
+        done: text(&object, "status").as_deref() == Some("completed"),
+        project: take_text(&mut object, "project"),
+        due: text(&object, "due").as_deref().and_then(parse_timestamp),
+        scheduled: text(&object, "scheduled").as_deref().and_then(parse_timestamp),
+        priority: priority.unwrap_or_default(),
+        recurrence: text(&object, "recur").as_deref().and_then(parse_recurrence),
+        tags,
+        created: text(&object, "entry").as_deref().and_then(parse_timestamp),
+        completed: text(&object, "end").as_deref().and_then(parse_timestamp),
+        uuid,
+        extra: Some(object).filter(|o| !o.is_empty()),
+    }
+}
+
+/// Write the tasks as the JSON array `task import` reads
+pub fn write(mut writer: impl Write, tasks: &[ExportTask]) -> Result<()> {
+    let uuids: HashMap<i64, &str> = tasks
+        .iter()
+        .map(|export| (export.task.id, export.task.uuid.as_str()))
+        .collect();
+    writeln!(writer, "[")?;
+    for (index, export) in tasks.iter().enumerate() {
+        let separator = if index + 1 < tasks.len() { "," } else { "" };
+        let object = Value::Object(format_task(export, &uuids));
+        writeln!(writer, "{object}{separator}")?;
+    }
+    writeln!(writer, "]")?;
+    Ok(())
+}
+
+/// Object of a task, `uuids` are those of the subtasks it depends on
+pub fn format_task(export: &ExportTask, uuids: &HashMap<i64, &str>) -> Map<String, Value> {
+    let task = export.task;
+    let mut object = task.extra.as_ref().map(|extra| extra.0.clone()).unwrap_or_default();
+    object.insert("uuid".to_string(), task.uuid.as_str().into());
+    object.insert("description".to_string(), task.title.as_str().into());
+    let status = match text(&object, "status").as_deref() {
+        _ if task.done => "completed".to_string(),
+        Some(status @ ("waiting" | "recurring")) => status.to_string(),
+        _ => "pending".to_string(),
+    };
+    object.insert("status".to_string(), status.into());
+
+    if task.project_id == PROJECT_INBOX_ID {
+        object.remove("project");
+    } else {
+        object.insert("project".to_string(), export.project.into());
+    }
+    if task.tags.is_empty() {
+        object.remove("tags");
+    } else {
+        object.insert("tags".to_string(), task.tags.0.clone().into());
+    }
+
+    let mut depends = depends(object.remove("depends"));
+    for id in task.sub_task_ids.iter() {
+        if let Some(uuid) = uuids.get(id).filter(|uuid| !depends.iter().any(|d| d == *uuid)) {
+            depends.push(uuid.to_string());
+        }
+    }
+    if !depends.is_empty() {
+        object.insert("depends".to_string(), depends.into());
+    }
+
+    merge(&mut object, "entry", task.created, parse_timestamp, format_timestamp);
+    let completed = task.completed.filter(|_| task.done);
+    merge(&mut object, "end", completed, parse_timestamp, format_timestamp);
+    merge(&mut object, "due", task.due, parse_timestamp, format_timestamp);
+    merge(&mut object, "scheduled", task.scheduled, parse_timestamp, format_timestamp);
+    let priority = Some(task.priority).filter(|p| *p != Priority::None);
+    merge(&mut object, "priority", priority, parse_priority, format_priority);
+    if task.priority == Priority::Urgent {
+        object.insert(URGENT.to_string(), true.into());
+    } else {
+        object.remove(URGENT);
+    }
+    let recurrence = task.recurrence.clone();
+    merge(&mut object, "recur", recurrence, parse_recurrence, |r| format_recurrence(&r));
+
+    let description = task.description.as_deref().unwrap_or_default();
+    let lines = annotation_lines(description);
+    if lines.join("\n") == description {
+        object.remove(DESCRIPTION);
+    } else {
+        object.insert(DESCRIPTION.to_string(), description.into());
+    }
+    if annotations(&object) != lines {
+        let entry = text(&object, "entry")
+            .unwrap_or_else(|| Utc::now().format(TIMESTAMP).to_string());
+        let annotations: Vec<Value> = lines
+            .iter()
+            .map(|line| serde_json::json!({ "entry": entry, "description": line }))
+            .collect();
+        if annotations.is_empty() {
+            object.remove("annotations");
+        } else {
+            object.insert("annotations".to_string(), annotations.into());
+        }
+    }
+    object
+}
+
+/// Set `key` to `value`, keeping the original when it still reads as `value`
+///
+/// Taskwarrior keeps times and twodo only days, so unchanged dates keep their time.
+fn merge<T: PartialEq>(
+    object: &mut Map<String, Value>,
+    key: &str,
+    value: Option<T>,
+    parse: impl Fn(&str) -> Option<T>,
+    format: impl Fn(T) -> String,
+) {
+    let original = object.get(key).and_then(Value::as_str).map(parse);
+    match (original, value) {
+        (Some(Some(original)), Some(value)) if original == value => {}
+        // Values twodo does not understand are left alone
+        (Some(None), None) => {}
+        (_, Some(value)) => {
+            object.insert(key.to_string(), format(value).into());
+        }
+        (_, None) => {
+            object.remove(key);
+        }
+    }
+}
+
+fn text(object: &Map<String, Value>, key: &str) -> Option<String> {
+    object.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn take_text(object: &mut Map<String, Value>, key: &str) -> Option<String> {
+    match object.remove(key) {
+        Some(Value::String(text)) => Some(text),
+        _ => None,
+    }
+}
+
+fn annotations(object: &Map<String, Value>) -> Vec<&str> {
+    let Some(Value::Array(annotations)) = object.get("annotations") else {
+        return Vec::new();
+    };
+    annotations
+        .iter()
+        .filter_map(|a| a.get("description").and_then(Value::as_str))
+        .collect()
+}
+
+/// Lines of a description that make an annotation, Taskwarrior has no empty ones
+fn annotation_lines(description: &str) -> Vec<&str> {
+    description.lines().filter(|line| !line.trim().is_empty()).collect()
+}
+
+/// UUIDs of `depends`, a list or a comma separated string in older versions
+fn depends(value: Option<Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(depends)) => depends
+            .split(',')
+            .filter(|uuid| !uuid.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Some(Value::Array(depends)) => depends
+            .into_iter()
+            .filter_map(|uuid| uuid.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_priority(value: &str) -> Option<Priority> {
+    match value {
+        "H" => Some(Priority::High),
+        "M" => Some(Priority::Medium),
+        "L" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+fn format_priority(priority: Priority) -> String {
+    match priority {
+        Priority::Urgent | Priority::High => "H",
+        Priority::Medium => "M",
+        Priority::Low | Priority::None => "L",
+    }
+    .to_string()
+}
+
+/// RRULE of a `recur` value like `weekly`, `biweekly` or `3d`
+fn parse_recurrence(value: &str) -> Option<String> {
+    let rule = match value {
+        "biweekly" | "fortnight" => "every 2 weeks".to_string(),
+        "quarterly" => "every 3 months".to_string(),
+        "semiannual" => "every 6 months".to_string(),
+        _ => {
+            let digits = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+            let (interval, unit) = value.split_at(digits);
+            let unit = match unit {
+                "d" | "day" | "days" => "days",
+                "w" | "wk" | "wks" | "week" | "weeks" => "weeks",
+                "mo" | "mos" | "month" | "months" => "months",
+                "y" | "yr" | "yrs" | "year" | "years" => "years",
+                _ => return value.parse::<Recurrence>().ok().map(|r| r.to_string()),
+            };
+            format!("every {} {unit}", if interval.is_empty() { "1" } else { interval })
+        }
+    };
+    rule.parse::<Recurrence>().ok().map(|r| r.to_string())
+}
+
+/// The `recur` value of a rule, the days of the week and month are not kept
+fn format_recurrence(rule: &str) -> String {
+    let Ok(recurrence) = rule.parse::<Recurrence>() else {
+        return rule.to_string();
+    };
+    if "weekdays".parse::<Recurrence>().is_ok_and(|weekdays| weekdays == recurrence) {
+        return "weekdays".to_string();
+    }
+    let (name, unit) = match recurrence.frequency {
+        Frequency::Daily => ("daily", "d"),
+        Frequency::Weekly => ("weekly", "w"),
+        Frequency::Monthly => ("monthly", "mo"),
+        Frequency::Yearly => ("yearly", "y"),
+    };
+    match recurrence.interval {
+        1 => name.to_string(),
+        interval => format!("{interval}{unit}"),
+    }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        // -- Setup & Fixtures
+        let content = r#"[
+{"id":1,"uuid":"a1","description":"Plan the trip","status":"pending","project":"Travel",
+ "priority":"H","recur":"biweekly","due":"20250801T120000Z","depends":["b2","zz"],
+ "annotations":[{"entry":"20250730T120000Z","description":"Book the flights"},
+                {"entry":"20250730T130000Z","description":"and the hotel"}],
+ "urgency":8.2,"estimate":"3h"},
+{"id":0,"uuid":"b2","description":"Pack","status":"completed","tags":["home"],
+ "end":"20250731T120000Z"},
+{"id":0,"uuid":"c3","description":"Gone","status":"deleted"}
+]"#;
+
+        // -- Exec
+        let tasks = parse(content)?;
+
+        // -- Check
+        assert_eq!(tasks.len(), 2);
+        let (trip, pack) = (&tasks[0], &tasks[1]);
+        assert_eq!(trip.title, "Plan the trip");
+        assert_eq!(trip.description.as_deref(), Some("Book the flights\nand the hotel"));
+        assert_eq!(trip.project.as_deref(), Some("Travel"));
+        assert_eq!(trip.priority, Priority::High);
+        assert_eq!(trip.recurrence.as_deref(), Some("FREQ=WEEKLY;INTERVAL=2"));
+        assert!(trip.due.is_some());
+        let extra = trip.extra.as_ref().ok_or("no extra")?;
+        assert_eq!(extra["estimate"], "3h");
+        assert_eq!(extra["depends"], serde_json::json!(["zz"]));
+        assert!(!extra.contains_key("urgency") && !extra.contains_key("description"));
+
+        assert_eq!(pack.parent.as_deref(), Some("a1"));
+        assert!(pack.done);
+        assert_eq!(pack.tags, vec!["home"]);
+        assert!(pack.completed.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_urgent_round_trip() -> Result<()> {
+        // -- Setup & Fixtures
+        let task = crate::objects::Task {
+            title: "Fix the leak".to_string(),
+            priority: Priority::Urgent,
+            uuid: "a1".to_string(),
+            ..Default::default()
+        };
+        let export = ExportTask {
+            task: &task,
+            project: "INBOX",
+        };
+
+        // -- Exec
+        let object = format_task(&export, &HashMap::new());
+
+        // -- Check
+        assert_eq!(object["priority"], "H");
+        let parsed = parse(&Value::Object(object.clone()).to_string())?;
+        assert_eq!(parsed[0].priority, Priority::Urgent);
+        assert!(!parsed[0].extra.as_ref().ok_or("no extra")?.contains_key(URGENT));
+
+        // A priority changed in Taskwarrior wins
+        let mut changed = object;
+        changed.insert("priority".to_string(), "M".into());
+        let parsed = parse(&Value::Object(changed).to_string())?;
+        assert_eq!(parsed[0].priority, Priority::Medium);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_recurrence() {
+        for (value, expected) in [
+            ("weekly", "weekly"),
+            ("3d", "3d"),
+            ("2wks", "2w"),
+            ("quarterly", "3mo"),
+            ("weekdays", "weekdays"),
+        ] {
+            let rule = parse_recurrence(value).unwrap_or_default();
+            assert_eq!(format_recurrence(&rule), expected, "{value}");
+        }
+        assert_eq!(parse_recurrence("P1W"), None);
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,364 @@
+use crate::{
+    constants::PROJECT_INBOX_ID,
+    exchange::{ExportTask, ImportTask},
+    objects::{Frequency, Priority, Recurrence},
+    Result,
+};
+use chrono::NaiveDate;
+use std::io::Write;
+
+/// Keys of the `key:value` words twodo reads
+const EXTENSIONS: &[&str] = &["due", "t", "rec", "id", "p", "desc", "pri"];
+
+/// Tasks of a [todo.txt](https://github.com/todotxt/todo.txt) file, one per line
+///
+/// ```text
+/// x 2025-08-02 2025-07-30 Call the plumber +Home @phone due:2025-08-01 pri:A
+/// (B) 2025-07-30 Plan the trip +Travel id:12 desc:Book%20the%20flights%0Aand%20the%20hotel
+/// ```
+///
+/// Priorities `(A)` to `(D)` map to urgent, high, medium and low, later letters to low. Besides
+/// `due:`, the common extensions `t:` for the scheduled date, `rec:` for recurrence and `id:`/`p:`
+/// for subtasks are read, along with `pri:` for the priority of done tasks and `desc:` for the
+/// percent-encoded description. Other words stay in the title. Projects, tags and title words are
+/// percent-decoded.
+pub fn parse(content: &str) -> Vec<ImportTask> {
+    content.lines().filter_map(parse_line).collect()
+}
+
+/// Task on a line, `None` when the line is blank
+pub fn parse_line(line: &str) -> Option<ImportTask> {
+    let mut words = line.split_whitespace().peekable();
+    words.peek()?;
+
+    let mut task = ImportTask::default();
+    if words.next_if_eq(&"x").is_some() {
+        task.done = true;
+        task.completed = words.next_if(|w| parse_date(w).is_some()).and_then(parse_date);
+    }
+    if let Some(priority) = words.peek().and_then(|w| parse_priority(w)) {
+        words.next();
+        task.priority = priority;
+    }
+    task.created = words.next_if(|w| parse_date(w).is_some()).and_then(parse_date);
+
+    // The last project is the one of the task, others stay in the title
+    let words: Vec<&str> = words.collect();
+    let project_index = words.iter().rposition(|w| w.len() > 1 && w.starts_with('+'));
+    let mut title = Vec::new();
+    for (index, word) in words.into_iter().enumerate() {
+        if Some(index) == project_index {
+            task.project = Some(decode(&word[1..]));
+        } else if let Some(tag) = word.strip_prefix('@').filter(|tag| !tag.is_empty()) {
+            task.tags.push(decode(tag));
+        } else if !parse_extension(&mut task, word) {
+            title.push(decode(word));
+        }
+    }
+    task.title = title.join(" ");
+    Some(task)
+}
+
+/// Fill in the field of a `key:value` word, `false` when it is not one twodo knows
+///
+/// Values that do not parse, and repeated keys, are left in the title.
+fn parse_extension(task: &mut ImportTask, word: &str) -> bool {
+    let Some((key, value)) = word.split_once(':') else {
+        return false;
+    };
+    let text = Some(value).filter(|v| !v.is_empty());
+    match key {
+        "due" if task.due.is_none() => set(&mut task.due, parse_date(value)),
+        "t" if task.scheduled.is_none() => set(&mut task.scheduled, parse_date(value)),
+        "rec" if task.recurrence.is_none() => set(&mut task.recurrence, parse_recurrence(value)),
+        "id" if task.key.is_none() => set(&mut task.key, text.map(str::to_string)),
+        "p" if task.parent.is_none() => set(&mut task.parent, text.map(str::to_string)),
+        "desc" if task.description.is_none() => set(&mut task.description, text.map(decode)),
+        "pri" if task.priority == Priority::None => {
+            match parse_priority(&format!("({value})")) {
+                Some(priority) => task.priority = priority,
+                None => return false,
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Store `value` in `field`, `false` when there is none
+fn set<T>(field: &mut Option<T>, value: Option<T>) -> bool {
+    *field = value;
+    field.is_some()
+}
+
+pub fn write(mut writer: impl Write, tasks: &[ExportTask]) -> Result<()> {
+    for task in tasks {
+        writeln!(writer, "{}", format_task(task))?;
+    }
+    Ok(())
+}
+
+pub fn format_task(export: &ExportTask) -> String {
+    let task = export.task;
+    let mut words = Vec::new();
+    if task.done {
+        words.push("x".to_string());
+        words.extend(task.completed.map(|d| d.to_string()));
+    } else {
+        words.extend(priority_letter(task.priority).map(|p| format!("({p})")));
+    }
+    // A lone date after `x` is the completion date
+    if !task.done || task.completed.is_some() {
+        words.extend(task.created.map(|d| d.to_string()));
+    }
+    let title: Vec<String> = task
+        .title
+        .split_whitespace()
+        .enumerate()
+        .map(|(index, word)| escape_word(word, index == 0))
+        .collect();
+    words.push(title.join(" "));
+
+    if task.project_id != PROJECT_INBOX_ID {
+        words.push(format!("+{}", encode(export.project)));
+    }
+    for tag in task.tags.iter() {
+        words.push(format!("@{}", encode(tag)));
+    }
+    words.extend(task.due.map(|d| format!("due:{d}")));
+    words.extend(task.scheduled.map(|d| format!("t:{d}")));
+    words.extend(task.recurrence.as_deref().map(|r| format!("rec:{}", format_recurrence(r))));
+    if task.done {
+        words.extend(priority_letter(task.priority).map(|p| format!("pri:{p}")));
+    }
+    if !task.sub_task_ids.is_empty() {
+        words.push(format!("id:{}", task.id));
+    }
+    words.extend(task.parent_id.map(|id| format!("p:{id}")));
+    if let Some(description) = task.description.as_deref().filter(|d| !d.is_empty()) {
+        words.push(format!("desc:{}", encode(description)));
+    }
+    words.join(" ")
+}
+
+/// Percent-encode a title word, and the first character of one `parse_line` would read as a
+/// project, tag, extension, or as the done mark, priority or date at the start of the title
+fn escape_word(word: &str, first: bool) -> String {
+    let word = encode(word);
+    let key = word.split_once(':').map(|(key, _)| key);
+    let reserved = (word.len() > 1 && word.starts_with(['+', '@']))
+        || key.is_some_and(|key| EXTENSIONS.contains(&key))
+        || (first && word == "x")
+        || (first && (parse_priority(&word).is_some() || parse_date(&word).is_some()));
+    match word.chars().next() {
+        Some(c) if reserved => format!("%{:02X}{}", c as u8, &word[c.len_utf8()..]),
+        _ => word,
+    }
+}
+
+fn parse_date(word: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(word, "%Y-%m-%d").ok()
+}
+
+/// Priority in the `(A)` form
+fn parse_priority(word: &str) -> Option<Priority> {
+    let letter = word.strip_prefix('(')?.strip_suffix(')')?;
+    match letter {
+        "A" => Some(Priority::Urgent),
+        "B" => Some(Priority::High),
+        "C" => Some(Priority::Medium),
+        _ if letter.len() == 1 && letter.chars().all(|c| c.is_ascii_uppercase()) => {
+            Some(Priority::Low)
+        }
+        _ => None,
+    }
+}
+
+fn priority_letter(priority: Priority) -> Option<char> {
+    match priority {
+        Priority::None => None,
+        Priority::Low => Some('D'),
+        Priority::Medium => Some('C'),
+        Priority::High => Some('B'),
+        Priority::Urgent => Some('A'),
+    }
+}
+
+/// RRULE of a `rec:` value, either the todo.txt form like `2w` or an RRULE
+fn parse_recurrence(value: &str) -> Option<String> {
+    // twodo always recurs from the due date, as strict `+` recurrence does
+    let value = value.strip_prefix('+').unwrap_or(value);
+    let unit = match value.chars().last()? {
+        'd' => "days",
+        'w' => "weeks",
+        'm' => "months",
+        'y' => "years",
+        _ => return value.parse::<Recurrence>().ok().map(|r| r.to_string()),
+    };
+    let interval: u32 = value[..value.len() - 1].parse().ok()?;
+    let recurrence: Recurrence = format!("every {interval} {unit}").parse().ok()?;
+    Some(recurrence.to_string())
+}
+
+/// The todo.txt form of simple rules, other rules are kept as RRULE
+fn format_recurrence(rule: &str) -> String {
+    match rule.parse::<Recurrence>() {
+        Ok(r) if r.by_day.is_empty() && r.by_month_day.is_none() && r.by_month.is_none() => {
+            let unit = match r.frequency {
+                Frequency::Daily => 'd',
+                Frequency::Weekly => 'w',
+                Frequency::Monthly => 'm',
+                Frequency::Yearly => 'y',
+            };
+            format!("{}{unit}", r.interval)
+        }
+        _ => rule.to_string(),
+    }
+}
+
+/// Percent-encode what would split the word or the line
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '%' | ' ' | '\t' | '\n' | '\r' => encoded.push_str(&format!("%{:02X}", c as u8)),
+            c => encoded.push(c),
+        }
+    }
+    encoded
+}
+
+fn decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let hex = tail.get(..2).and_then(|h| std::str::from_utf8(h).ok());
+        match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(decoded) if byte == b'%' => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    fn date(s: &str) -> Result<NaiveDate> {
+        Ok(NaiveDate::parse_from_str(s, "%Y-%m-%d")?)
+    }
+
+    #[test]
+    fn test_parse_line() -> Result<()> {
+        // -- Setup & Fixtures
+        let line = "x 2025-08-02 2025-07-30 Call +plumber about http://example.com +Home \
+            @phone due:2025-08-01 pri:A rec:+1w foo:bar desc:Ask%20for%0Aa%20quote";
+
+        // -- Exec
+        let task = parse_line(line).ok_or("no task")?;
+
+        // -- Check
+        assert_eq!(
+            task,
+            ImportTask {
+                title: "Call +plumber about http://example.com foo:bar".to_string(),
+                description: Some("Ask for\na quote".to_string()),
+                done: true,
+                project: Some("Home".to_string()),
+                due: Some(date("2025-08-01")?),
+                priority: Priority::Urgent,
+                recurrence: Some("FREQ=WEEKLY".to_string()),
+                tags: vec!["phone".to_string()],
+                created: Some(date("2025-07-30")?),
+                completed: Some(date("2025-08-02")?),
+                ..Default::default()
+            }
+        );
+        assert_eq!(parse_line("   "), None);
+        assert_eq!(parse_line("(Z) xylophone").ok_or("no task")?.priority, Priority::Low);
+        assert_eq!(parse_line("x done").ok_or("no task")?.title, "done");
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_task() -> Result<()> {
+        // -- Setup & Fixtures
+        let task = crate::objects::Task {
+            id: 7,
+            title: "Plan the trip".to_string(),
+            description: Some("Book 100% of\nthe flights".to_string()),
+            project_id: 3,
+            parent_id: Some(2),
+            sub_task_ids: sqlx::types::Json(vec![8]),
+            scheduled: Some(date("2025-08-10")?),
+            priority: Priority::High,
+            recurrence: Some("FREQ=MONTHLY;BYMONTHDAY=15".to_string()),
+            tags: sqlx::types::Json(vec!["deep work".to_string()]),
+            created: Some(date("2025-07-30")?),
+            ..Default::default()
+        };
+        let export = ExportTask {
+            task: &task,
+            project: "Summer Travel",
+        };
+
+        // -- Exec
+        let line = format_task(&export);
+
+        // -- Check
+        assert_eq!(
+            line,
+            "(B) 2025-07-30 Plan the trip +Summer%20Travel @deep%20work t:2025-08-10 \
+            rec:FREQ=MONTHLY;BYMONTHDAY=15 id:7 p:2 desc:Book%20100%25%20of%0Athe%20flights"
+        );
+        let parsed = parse_line(&line).ok_or("no task")?;
+        assert_eq!(parsed.project.as_deref(), Some("Summer Travel"));
+        assert_eq!(parsed.tags, task.tags.0);
+        assert_eq!(parsed.description, task.description);
+        assert_eq!(parsed.recurrence, task.recurrence);
+        assert_eq!(parsed.key.as_deref(), Some("7"));
+        assert_eq!(parsed.parent.as_deref(), Some("2"));
+
+        // Titles that read as other fields come back as they were
+        for title in [
+            "Email @bob re +launch",
+            "x marks the spot",
+            "(A) is the grade",
+            "2025-08-01 is the deadline",
+            "Set due:friday and pri:A in rec:1w with 100% effort",
+        ] {
+            let task = crate::objects::Task {
+                title: title.to_string(),
+                project_id: PROJECT_INBOX_ID,
+                ..Default::default()
+            };
+            let export = ExportTask {
+                task: &task,
+                project: "INBOX",
+            };
+            let parsed = parse_line(&format_task(&export)).ok_or("no task")?;
+            assert_eq!(
+                parsed,
+                ImportTask {
+                    title: title.to_string(),
+                    ..Default::default()
+                },
+                "{title}"
+            );
+        }
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
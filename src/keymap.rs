@@ -0,0 +1,697 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+/// A key press, written like in vim: `j`, `G`, `<C-r>`, `<Enter>` or `<Space>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+/// Set of bindings the keys are looked up in, depending on what has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyContext {
+    /// Fallback for the task and project panes
+    Global,
+    Task,
+    Project,
+    MoveTask,
+    Trash,
+    AddTask,
+    AddProject,
+    Search,
+    /// Yes or no question before a delete
+    Confirm,
+}
+
+/// What a key sequence does, its meaning depends on the context it is bound in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Removes a default binding
+    #[serde(rename = "none")]
+    Unbound,
+    Quit,
+    Undo,
+    Redo,
+    FocusProject,
+    FocusTask,
+    OpenTrash,
+    Help,
+    SelectNext,
+    SelectPrev,
+    SelectFirst,
+    SelectLast,
+    Search,
+    ToggleDetail,
+    Add,
+    AddSubTask,
+    AddSiblingTask,
+    Edit,
+    /// Edit the title and description in `$EDITOR`
+    OpenEditor,
+    Delete,
+    Reload,
+    ToggleDone,
+    CyclePriority,
+    Move,
+    ReorderUp,
+    ReorderDown,
+    Indent,
+    Outdent,
+    ToggleFold,
+    OpenFold,
+    CloseFold,
+    FoldAll,
+    UnfoldAll,
+    Restore,
+    Cancel,
+    Confirm,
+    NextField,
+}
+
+/// Key sequences bound to actions for each context
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: HashMap<KeyContext, HashMap<Vec<Key>, Action>>,
+}
+
+/// Result of looking up the keys pressed so far
+#[derive(Debug, PartialEq)]
+pub enum Lookup {
+    Action(KeyContext, Action),
+    /// The keys start a longer sequence
+    Pending,
+    Unbound,
+}
+
+/// A binding as printed by `twodo keys`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Binding {
+    pub context: KeyContext,
+    pub keys: String,
+    pub action: Action,
+}
+
+// region:    --- Key
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        // Shift is already part of the character, and of BackTab
+        let modifiers = match event.code {
+            KeyCode::Char(_) | KeyCode::BackTab => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+}
+
+impl Key {
+    fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn named(name: &str) -> Option<KeyCode> {
+        let code = match name.to_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "lt" => KeyCode::Char('<'),
+            "gt" => KeyCode::Char('>'),
+            "enter" | "cr" | "return" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "bs" | "backspace" => KeyCode::Backspace,
+            "del" | "delete" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name => match name.strip_prefix('f').map(str::parse) {
+                Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                _ => return None,
+            },
+        };
+        Some(code)
+    }
+
+    /// Parse the inside of `<...>`, like `C-r` or `Enter`
+    fn parse_special(special: &str) -> Option<Self> {
+        let mut parts = special.split('-').collect::<Vec<_>>();
+        // `<C-->` binds Ctrl and minus
+        if special.ends_with("--") {
+            parts.pop();
+            parts.pop();
+            parts.push("-");
+        }
+        let name = parts.pop()?;
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in parts {
+            match modifier {
+                "C" | "c" => modifiers |= KeyModifiers::CONTROL,
+                "A" | "a" | "M" | "m" => modifiers |= KeyModifiers::ALT,
+                "S" | "s" => modifiers |= KeyModifiers::SHIFT,
+                _ => return None,
+            }
+        }
+
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => Self::named(name)?,
+        };
+        let key = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => Self {
+                code: KeyCode::Char(c.to_ascii_uppercase()),
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => Self {
+                code: KeyCode::BackTab,
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            code => Self { code, modifiers },
+        };
+        Some(key)
+    }
+
+    /// Parse a sequence of keys like `gg`, `za` or `<C-w>j`
+    pub fn parse_sequence(sequence: &str) -> Result<Vec<Self>, String> {
+        let mut keys = Vec::new();
+        let mut rest = sequence;
+        while let Some(c) = rest.chars().next() {
+            // A `<` without a closing `>` is the key itself, as in `<` and `<<`
+            let special = rest
+                .strip_prefix('<')
+                .and_then(|r| r.find('>').map(|end| (&r[..end], &r[end + 1..])))
+                .filter(|(special, _)| !special.is_empty());
+            match special {
+                Some((special, after)) => {
+                    let key = Self::parse_special(special)
+                        .ok_or_else(|| format!("unknown key <{special}> in {sequence:?}"))?;
+                    keys.push(key);
+                    rest = after;
+                }
+                None => {
+                    keys.push(Self::new(KeyCode::Char(c)));
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        if keys.is_empty() {
+            return Err("empty key sequence".to_string());
+        }
+        Ok(keys)
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "S-Tab".to_string(),
+            KeyCode::Backspace => "BS".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::F(n) => format!("F{n}"),
+            code => format!("{code:?}"),
+        };
+        let mut prefix = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push_str("C-");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("A-");
+        }
+        match self.code {
+            KeyCode::Char(c) if c != ' ' && prefix.is_empty() => write!(f, "{name}"),
+            _ => write!(f, "<{prefix}{name}>"),
+        }
+    }
+}
+
+fn sequence_string(keys: &[Key]) -> String {
+    keys.iter().map(Key::to_string).collect()
+}
+
+// endregion: --- Key
+
+// region:    --- Context
+
+impl KeyContext {
+    pub const ALL: [KeyContext; 9] = [
+        KeyContext::Global,
+        KeyContext::Task,
+        KeyContext::Project,
+        KeyContext::MoveTask,
+        KeyContext::Trash,
+        KeyContext::AddTask,
+        KeyContext::AddProject,
+        KeyContext::Search,
+        KeyContext::Confirm,
+    ];
+
+    /// Contexts searched, in order, when this one has focus
+    pub fn chain(self) -> &'static [KeyContext] {
+        match self {
+            KeyContext::Task => &[KeyContext::Task, KeyContext::Global],
+            KeyContext::Project => &[KeyContext::Project, KeyContext::Global],
+            KeyContext::Global => &[KeyContext::Global],
+            KeyContext::MoveTask => &[KeyContext::MoveTask],
+            KeyContext::Trash => &[KeyContext::Trash],
+            KeyContext::AddTask => &[KeyContext::AddTask],
+            KeyContext::AddProject => &[KeyContext::AddProject],
+            KeyContext::Search => &[KeyContext::Search],
+            KeyContext::Confirm => &[KeyContext::Confirm],
+        }
+    }
+
+    /// Actions that can be bound in this context
+    pub fn actions(self) -> &'static [Action] {
+        use Action::*;
+        match self {
+            KeyContext::Global => &[Quit, Undo, Redo, FocusProject, FocusTask, OpenTrash, Help],
+            KeyContext::Task => &[
+                SelectNext,
+                SelectPrev,
+                SelectFirst,
+                SelectLast,
+                Search,
+                ToggleDetail,
+                FocusProject,
+                Add,
+                AddSubTask,
+                AddSiblingTask,
+                Edit,
+                OpenEditor,
+                Delete,
+                Reload,
+                ToggleDone,
+                CyclePriority,
+                Move,
+                ReorderUp,
+                ReorderDown,
+                Indent,
+                Outdent,
+                ToggleFold,
+                OpenFold,
+                CloseFold,
+                FoldAll,
+                UnfoldAll,
+            ],
+            KeyContext::Project => &[
+                SelectNext,
+                SelectPrev,
+                SelectFirst,
+                SelectLast,
+                FocusTask,
+                Add,
+                Edit,
+                Delete,
+                Reload,
+            ],
+            KeyContext::MoveTask => &[Cancel, Confirm, SelectNext, SelectPrev],
+            KeyContext::Trash => &[Cancel, Restore, SelectNext, SelectPrev],
+            KeyContext::AddTask => &[Cancel, Confirm, NextField, OpenEditor],
+            KeyContext::AddProject => &[Cancel, Confirm, NextField],
+            KeyContext::Search | KeyContext::Confirm => &[Cancel, Confirm],
+        }
+    }
+
+    fn default_bindings(self) -> &'static [(&'static str, Action)] {
+        use Action::*;
+        match self {
+            KeyContext::Global => &[
+                ("<Esc>", Quit),
+                ("q", Quit),
+                ("<C-c>", Quit),
+                ("u", Undo),
+                ("<C-r>", Redo),
+                ("1", FocusProject),
+                ("2", FocusTask),
+                ("T", OpenTrash),
+                ("?", Help),
+            ],
+            KeyContext::Task => &[
+                ("j", SelectNext),
+                ("k", SelectPrev),
+                ("g", SelectFirst),
+                ("G", SelectLast),
+                ("/", Search),
+                ("<Enter>", ToggleDetail),
+                ("<Tab>", FocusProject),
+                ("i", Add),
+                ("s", AddSubTask),
+                ("a", AddSiblingTask),
+                ("e", Edit),
+                ("E", OpenEditor),
+                ("x", Delete),
+                ("r", Reload),
+                ("<Space>", ToggleDone),
+                ("p", CyclePriority),
+                ("m", Move),
+                ("K", ReorderUp),
+                ("J", ReorderDown),
+                (">", Indent),
+                ("<", Outdent),
+                ("za", ToggleFold),
+                ("zo", OpenFold),
+                ("zc", CloseFold),
+                ("zM", FoldAll),
+                ("zR", UnfoldAll),
+            ],
+            KeyContext::Project => &[
+                ("j", SelectNext),
+                ("k", SelectPrev),
+                ("g", SelectFirst),
+                ("G", SelectLast),
+                ("<Tab>", FocusTask),
+                ("i", Add),
+                ("a", Add),
+                ("e", Edit),
+                ("x", Delete),
+                ("r", Reload),
+            ],
+            KeyContext::MoveTask => &[
+                ("<Esc>", Cancel),
+                ("<C-c>", Cancel),
+                ("<Enter>", Confirm),
+                ("j", SelectNext),
+                ("<Down>", SelectNext),
+                ("k", SelectPrev),
+                ("<Up>", SelectPrev),
+            ],
+            KeyContext::Trash => &[
+                ("<Esc>", Cancel),
+                ("q", Cancel),
+                ("<C-c>", Cancel),
+                ("<Enter>", Restore),
+                ("r", Restore),
+                ("j", SelectNext),
+                ("<Down>", SelectNext),
+                ("k", SelectPrev),
+                ("<Up>", SelectPrev),
+            ],
+            KeyContext::AddTask => &[
+                ("<Esc>", Cancel),
+                ("<C-c>", Cancel),
+                ("<C-Space>", Confirm),
+                ("<Tab>", NextField),
+                ("<C-o>", OpenEditor),
+            ],
+            KeyContext::AddProject => &[
+                ("<Esc>", Cancel),
+                ("<C-c>", Cancel),
+                ("<C-Space>", Confirm),
+                ("<Tab>", NextField),
+            ],
+            KeyContext::Search => &[
+                ("<Esc>", Cancel),
+                ("<C-c>", Cancel),
+                ("<Enter>", Confirm),
+            ],
+            KeyContext::Confirm => &[
+                ("y", Confirm),
+                ("<Enter>", Confirm),
+                ("n", Cancel),
+                ("q", Cancel),
+                ("<Esc>", Cancel),
+                ("<C-c>", Cancel),
+            ],
+        }
+    }
+}
+
+impl Action {
+    /// Short description for the help popup and the footer
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Unbound => "nothing",
+            Action::Quit => "quit",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::FocusProject => "projects",
+            Action::FocusTask => "tasks",
+            Action::OpenTrash => "trash",
+            Action::Help => "help",
+            Action::SelectNext => "next",
+            Action::SelectPrev => "previous",
+            Action::SelectFirst => "first",
+            Action::SelectLast => "last",
+            Action::Search => "search",
+            Action::ToggleDetail => "details",
+            Action::Add => "add",
+            Action::AddSubTask => "add subtask",
+            Action::AddSiblingTask => "add sibling",
+            Action::Edit => "edit",
+            Action::OpenEditor => "edit in $EDITOR",
+            Action::Delete => "delete",
+            Action::Reload => "reload",
+            Action::ToggleDone => "done",
+            Action::CyclePriority => "priority",
+            Action::Move => "move",
+            Action::ReorderUp => "move up",
+            Action::ReorderDown => "move down",
+            Action::Indent => "indent",
+            Action::Outdent => "outdent",
+            Action::ToggleFold => "toggle fold",
+            Action::OpenFold => "open fold",
+            Action::CloseFold => "close fold",
+            Action::FoldAll => "fold all",
+            Action::UnfoldAll => "unfold all",
+            Action::Restore => "restore",
+            Action::Cancel => "cancel",
+            Action::Confirm => "confirm",
+            Action::NextField => "next field",
+        }
+    }
+}
+
+impl Display for KeyContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = serde_json::to_value(self).map_err(|_| std::fmt::Error)?;
+        f.pad(name.as_str().unwrap_or_default())
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = serde_json::to_value(self).map_err(|_| std::fmt::Error)?;
+        f.pad(name.as_str().unwrap_or_default())
+    }
+}
+
+// endregion: --- Context
+
+// region:    --- Keymap
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new()).expect("default keymap is valid")
+    }
+}
+
+impl Keymap {
+    /// The default bindings with `overrides` from the config applied on top
+    pub fn new(overrides: &BTreeMap<KeyContext, BTreeMap<String, Action>>) -> Result<Self, String> {
+        let mut bindings: HashMap<KeyContext, HashMap<Vec<Key>, Action>> = HashMap::new();
+        for context in KeyContext::ALL {
+            let context_bindings = bindings.entry(context).or_default();
+            for (sequence, action) in context.default_bindings() {
+                context_bindings.insert(Key::parse_sequence(sequence)?, *action);
+            }
+        }
+
+        for (context, overrides) in overrides {
+            let context_bindings = bindings.entry(*context).or_default();
+            for (sequence, action) in overrides {
+                let keys = Key::parse_sequence(sequence).map_err(|err| format!("keys.{context}: {err}"))?;
+                if *action == Action::Unbound {
+                    context_bindings.remove(&keys);
+                    continue;
+                }
+                if !context.actions().contains(action) {
+                    return Err(format!("keys.{context}: {action} cannot be bound in {context}"));
+                }
+                context_bindings.insert(keys, *action);
+            }
+        }
+
+        let keymap = Self { bindings };
+        keymap.check_prefixes()?;
+        Ok(keymap)
+    }
+
+    /// A sequence that starts a longer one could never be used
+    fn check_prefixes(&self) -> Result<(), String> {
+        for context in KeyContext::ALL {
+            let sequences = context
+                .chain()
+                .iter()
+                .flat_map(|c| self.bindings[c].keys())
+                .collect::<Vec<_>>();
+            for short in sequences.iter() {
+                if let Some(long) = sequences
+                    .iter()
+                    .find(|long| long.len() > short.len() && long.starts_with(short))
+                {
+                    return Err(format!(
+                        "keys.{context}: {} hides {}",
+                        sequence_string(short),
+                        sequence_string(long)
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Find what the keys pressed so far do in `context`
+    pub fn lookup(&self, context: KeyContext, keys: &[Key]) -> Lookup {
+        let mut pending = false;
+        for context in context.chain() {
+            for (sequence, action) in self.bindings[context].iter() {
+                if sequence == keys {
+                    return Lookup::Action(*context, *action);
+                }
+                pending |= sequence.starts_with(keys);
+            }
+        }
+        if pending {
+            return Lookup::Pending;
+        }
+        Lookup::Unbound
+    }
+
+    /// Key sequences bound to `action` in `context` or the contexts it falls back to,
+    /// shortest first
+    pub fn keys_for(&self, context: KeyContext, action: Action) -> Vec<String> {
+        let mut keys = context
+            .chain()
+            .iter()
+            .flat_map(|c| self.bindings[c].iter())
+            .filter(|(_, a)| **a == action)
+            .map(|(keys, _)| sequence_string(keys))
+            .collect::<Vec<_>>();
+        keys.sort_by_key(|k| (k.len(), k.clone()));
+        keys
+    }
+
+    /// Every binding, by context then action
+    pub fn bindings(&self) -> Vec<Binding> {
+        let mut bindings = KeyContext::ALL
+            .into_iter()
+            .flat_map(|context| {
+                self.bindings[&context].iter().map(move |(keys, action)| Binding {
+                    context,
+                    keys: sequence_string(keys),
+                    action: *action,
+                })
+            })
+            .collect::<Vec<_>>();
+        let order = |binding: &Binding| {
+            let action_index = binding
+                .context
+                .actions()
+                .iter()
+                .position(|a| *a == binding.action);
+            (binding.context, action_index, binding.keys.clone())
+        };
+        bindings.sort_by_key(order);
+        bindings
+    }
+}
+
+// endregion: --- Keymap
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Key {
+        Key { code, modifiers }
+    }
+
+    #[test]
+    fn test_parse_sequence() -> Result<()> {
+        let char_key = |c| key(KeyCode::Char(c), KeyModifiers::NONE);
+
+        assert_eq!(Key::parse_sequence("gg")?, vec![char_key('g'); 2]);
+        assert_eq!(
+            Key::parse_sequence("<C-r>")?,
+            vec![key(KeyCode::Char('r'), KeyModifiers::CONTROL)]
+        );
+        assert_eq!(Key::parse_sequence("<<")?, vec![char_key('<'); 2]);
+        assert_eq!(
+            Key::parse_sequence("<S-x><Enter>")?,
+            vec![char_key('X'), key(KeyCode::Enter, KeyModifiers::NONE)]
+        );
+        assert!(Key::parse_sequence("<C-nope>").is_err());
+        assert!(Key::parse_sequence("").is_err());
+
+        for sequence in ["za", "<C-Space>", "<S-Tab>", "<F5>", "<A-j>", "<Esc>"] {
+            assert_eq!(sequence_string(&Key::parse_sequence(sequence)?), sequence);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_keymap_lookup() -> Result<()> {
+        // -- Setup & Fixtures
+        let overrides = BTreeMap::from([(
+            KeyContext::Task,
+            BTreeMap::from([
+                ("n".to_string(), Action::SelectNext),
+                ("j".to_string(), Action::Unbound),
+                ("dd".to_string(), Action::Delete),
+            ]),
+        )]);
+
+        // -- Exec
+        let keymap = Keymap::new(&overrides)?;
+        let lookup = |keys: &str| -> Result<Lookup> {
+            Ok(keymap.lookup(KeyContext::Task, &Key::parse_sequence(keys)?))
+        };
+
+        // -- Check
+        assert_eq!(lookup("n")?, Lookup::Action(KeyContext::Task, Action::SelectNext));
+        assert_eq!(lookup("j")?, Lookup::Unbound);
+        assert_eq!(lookup("d")?, Lookup::Pending);
+        assert_eq!(lookup("dd")?, Lookup::Action(KeyContext::Task, Action::Delete));
+        assert_eq!(lookup("z")?, Lookup::Pending);
+        assert_eq!(lookup("u")?, Lookup::Action(KeyContext::Global, Action::Undo));
+
+        let hidden = BTreeMap::from([(
+            KeyContext::Global,
+            BTreeMap::from([("z".to_string(), Action::Redo)]),
+        )]);
+        assert!(Keymap::new(&hidden).is_err());
+        let misplaced = BTreeMap::from([(
+            KeyContext::Project,
+            BTreeMap::from([("f".to_string(), Action::ToggleFold)]),
+        )]);
+        assert!(Keymap::new(&misplaced).is_err());
+        Ok(())
+    }
+}
+
+// endregion: --- Tests
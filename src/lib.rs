@@ -12,8 +12,11 @@ pub use run::run;
 pub(crate) mod constants;
 
 pub mod cli;
+pub mod config;
 pub mod controller;
 pub mod error;
+pub mod exchange;
+pub mod keymap;
 pub mod objects;
 
 // endregion: --- Modules
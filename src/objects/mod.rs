@@ -1,10 +1,20 @@
 // region:    --- Modules
 mod task;
 mod project;
+mod priority;
+mod recurrence;
+mod tag;
+mod trash;
+mod workspace;
 
 // -- Flatten
 pub use task::Task;
 pub use project::Project;
+pub use priority::Priority;
+pub use recurrence::{Frequency, Recurrence};
+pub use tag::Tag;
+pub use trash::TrashItem;
+pub use workspace::Workspace;
 
 // endregion: --- Modules
 
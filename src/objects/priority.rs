@@ -0,0 +1,40 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    sqlx::Type,
+    ValueEnum,
+    Serialize,
+)]
+#[repr(i64)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    #[default]
+    None = 0,
+    Low = 1,
+    Medium = 2,
+    High = 3,
+    Urgent = 4,
+}
+
+impl Priority {
+    /// Next priority level, wrapping around from urgent to none
+    pub fn next(self) -> Self {
+        match self {
+            Priority::None => Priority::Low,
+            Priority::Low => Priority::Medium,
+            Priority::Medium => Priority::High,
+            Priority::High => Priority::Urgent,
+            Priority::Urgent => Priority::None,
+        }
+    }
+}
@@ -1,6 +1,7 @@
+use serde::Serialize;
 use sqlx::prelude::FromRow;
 
-#[derive(Debug, FromRow)]
+#[derive(Debug, FromRow, Serialize)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct Project {
     pub id: i64,
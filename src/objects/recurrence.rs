@@ -0,0 +1,337 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// Recurrence rule of a task, a subset of RFC 5545 RRULE
+///
+/// Rules are stored in their RRULE form, e.g. `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub by_month_day: Option<u32>,
+    pub by_month: Option<u32>,
+}
+
+impl Recurrence {
+    fn new(frequency: Frequency, interval: u32) -> Self {
+        Self {
+            frequency,
+            interval,
+            by_day: Vec::new(),
+            by_month_day: None,
+            by_month: None,
+        }
+    }
+
+    /// Pin the day of `due` that a short month would otherwise move for good
+    ///
+    /// Monthly rules keep a day past the 28th and yearly rules keep February 29, rules that
+    /// already name their days are left alone.
+    pub fn anchored(mut self, due: NaiveDate) -> Self {
+        let plain =
+            self.by_day.is_empty() && self.by_month_day.is_none() && self.by_month.is_none();
+        match self.frequency {
+            Frequency::Monthly if plain && due.day() > 28 => self.by_month_day = Some(due.day()),
+            Frequency::Yearly if plain && due.month() == 2 && due.day() == 29 => {
+                self.by_month = Some(2);
+                self.by_month_day = Some(29);
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// First occurrence strictly after `date`
+    pub fn next_after(&self, date: NaiveDate) -> NaiveDate {
+        let interval = self.interval as u64;
+        match self.frequency {
+            Frequency::Daily => {
+                let mut next = date + Days::new(interval);
+                while !self.by_day.is_empty() && !self.by_day.contains(&next.weekday()) {
+                    next = next + Days::new(1);
+                }
+                next
+            }
+            Frequency::Weekly if self.by_day.is_empty() => date + Days::new(7 * interval),
+            Frequency::Weekly => {
+                let weekday = date.weekday().num_days_from_monday();
+                let week_start = date - Days::new(weekday as u64);
+                let mut days = self
+                    .by_day
+                    .iter()
+                    .map(|d| d.num_days_from_monday())
+                    .collect::<Vec<_>>();
+                days.sort();
+                match days.iter().find(|&&d| d > weekday) {
+                    Some(&day) => week_start + Days::new(day as u64),
+                    None => week_start + Days::new(7 * interval + days[0] as u64),
+                }
+            }
+            Frequency::Monthly => match self.by_month_day {
+                Some(day) if clamp_day(date, 0, day) > date => clamp_day(date, 0, day),
+                Some(day) => clamp_day(date, self.interval, day),
+                None => clamp_day(date, self.interval, date.day()),
+            },
+            Frequency::Yearly => {
+                let month = self.by_month.unwrap_or(date.month());
+                let day = self.by_month_day.unwrap_or(date.day());
+                let start = date.with_day(1).unwrap().with_month(month).unwrap();
+                match clamp_day(start, 0, day) {
+                    next if next > date => next,
+                    _ => clamp_day(start, 12 * self.interval, day),
+                }
+            }
+        }
+    }
+}
+
+/// `day` of the month `months` after the month of `date`, clamped to the length of that month
+fn clamp_day(date: NaiveDate, months: u32, day: u32) -> NaiveDate {
+    let first = date.with_day(1).unwrap() + Months::new(months);
+    let last = (first + Months::new(1) - Days::new(1)).day();
+    first.with_day(day.min(last)).unwrap()
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    /// Parse shorthands like `daily`, `weekdays`, `every 2 weeks`, `monthly on the 15th`
+    /// or an RRULE like `FREQ=MONTHLY;BYMONTHDAY=15`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rule = s.trim().to_lowercase();
+        let words = rule.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            ["daily"] => Ok(Self::new(Frequency::Daily, 1)),
+            ["weekly"] => Ok(Self::new(Frequency::Weekly, 1)),
+            ["monthly"] => Ok(Self::new(Frequency::Monthly, 1)),
+            ["yearly"] | ["annually"] => Ok(Self::new(Frequency::Yearly, 1)),
+            ["weekdays"] => Ok(Self {
+                by_day: vec![
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri,
+                ],
+                ..Self::new(Frequency::Daily, 1)
+            }),
+            ["every", unit] => Ok(Self::new(parse_frequency(unit)?, 1)),
+            ["every", interval, unit] => Ok(Self::new(
+                parse_frequency(unit)?,
+                parse_interval(interval)?,
+            )),
+            ["monthly", "on", "the", day] | ["monthly", "on", day] => Ok(Self {
+                by_month_day: Some(parse_month_day(day)?),
+                ..Self::new(Frequency::Monthly, 1)
+            }),
+            _ if rule.starts_with("rrule:") || rule.starts_with("freq=") => parse_rrule(&rule),
+            _ => Err(format!(
+                "invalid recurrence '{s}', expected e.g. daily, weekdays, every 2 weeks, \
+                monthly on the 15th or an RRULE like FREQ=WEEKLY;BYDAY=MO"
+            )),
+        }
+    }
+}
+
+fn parse_frequency(unit: &str) -> Result<Frequency, String> {
+    match unit.trim_end_matches('s') {
+        "day" => Ok(Frequency::Daily),
+        "week" => Ok(Frequency::Weekly),
+        "month" => Ok(Frequency::Monthly),
+        "year" => Ok(Frequency::Yearly),
+        _ => Err(format!("invalid recurrence unit '{unit}'")),
+    }
+}
+
+fn parse_interval(interval: &str) -> Result<u32, String> {
+    match interval.parse::<u32>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!("invalid recurrence interval '{interval}'")),
+    }
+}
+
+fn parse_month_day(day: &str) -> Result<u32, String> {
+    let digits = day.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    match digits.parse::<u32>() {
+        Ok(n) if (1..=31).contains(&n) => Ok(n),
+        _ => Err(format!("invalid day of month '{day}'")),
+    }
+}
+
+fn parse_month(month: &str) -> Result<u32, String> {
+    match month.parse::<u32>() {
+        Ok(n) if (1..=12).contains(&n) => Ok(n),
+        _ => Err(format!("invalid month '{month}'")),
+    }
+}
+
+fn parse_weekday(day: &str) -> Result<Weekday, String> {
+    match day {
+        "mo" => Ok(Weekday::Mon),
+        "tu" => Ok(Weekday::Tue),
+        "we" => Ok(Weekday::Wed),
+        "th" => Ok(Weekday::Thu),
+        "fr" => Ok(Weekday::Fri),
+        "sa" => Ok(Weekday::Sat),
+        "su" => Ok(Weekday::Sun),
+        _ => Err(format!("invalid BYDAY value '{day}'")),
+    }
+}
+
+fn parse_rrule(rule: &str) -> Result<Recurrence, String> {
+    let rule = rule.trim_start_matches("rrule:");
+    let mut frequency = None;
+    let mut recurrence = Recurrence::new(Frequency::Daily, 1);
+
+    for part in rule.split(';').filter(|p| !p.is_empty()) {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| format!("invalid RRULE part '{part}'"))?;
+        match key {
+            "freq" => {
+                frequency = Some(match value {
+                    "daily" => Frequency::Daily,
+                    "weekly" => Frequency::Weekly,
+                    "monthly" => Frequency::Monthly,
+                    "yearly" => Frequency::Yearly,
+                    _ => return Err(format!("unsupported RRULE frequency '{value}'")),
+                })
+            }
+            "interval" => recurrence.interval = parse_interval(value)?,
+            "byday" => {
+                recurrence.by_day = value
+                    .split(',')
+                    .map(parse_weekday)
+                    .collect::<Result<_, _>>()?
+            }
+            "bymonthday" => recurrence.by_month_day = Some(parse_month_day(value)?),
+            "bymonth" => recurrence.by_month = Some(parse_month(value)?),
+            _ => return Err(format!("unsupported RRULE part '{}'", key.to_uppercase())),
+        }
+    }
+
+    recurrence.frequency = frequency.ok_or("RRULE is missing FREQ")?;
+    Ok(recurrence)
+}
+
+impl Display for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={frequency}")?;
+        if self.interval > 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days = self
+                .by_day
+                .iter()
+                .map(|d| d.to_string()[..2].to_uppercase())
+                .collect::<Vec<_>>();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(month) = self.by_month {
+            write!(f, ";BYMONTH={month}")?;
+        }
+        if let Some(day) = self.by_month_day {
+            write!(f, ";BYMONTHDAY={day}")?;
+        }
+        Ok(())
+    }
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_recurrence() {
+        // (input, canonical RRULE)
+        let cases = [
+            ("daily", "FREQ=DAILY"),
+            ("Weekdays", "FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR"),
+            ("every 2 weeks", "FREQ=WEEKLY;INTERVAL=2"),
+            ("every month", "FREQ=MONTHLY"),
+            ("monthly on the 15th", "FREQ=MONTHLY;BYMONTHDAY=15"),
+            ("RRULE:FREQ=WEEKLY;BYDAY=MO,FR", "FREQ=WEEKLY;BYDAY=MO,FR"),
+            ("FREQ=YEARLY;INTERVAL=1", "FREQ=YEARLY"),
+            ("FREQ=YEARLY;BYMONTHDAY=29;BYMONTH=2", "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29"),
+        ];
+        for (input, expected) in cases {
+            let recurrence: Recurrence = input.parse().unwrap();
+            assert_eq!(recurrence.to_string(), expected);
+            assert_eq!(expected.parse::<Recurrence>(), Ok(recurrence));
+        }
+
+        assert!("fortnightly".parse::<Recurrence>().is_err());
+        assert!("every 0 days".parse::<Recurrence>().is_err());
+        assert!("FREQ=DAILY;COUNT=3".parse::<Recurrence>().is_err());
+        assert!("INTERVAL=2".parse::<Recurrence>().is_err());
+    }
+
+    #[test]
+    fn test_next_after() {
+        // (rule, from, expected) where 2025-07-18 is a Friday
+        let cases = [
+            ("daily", date(2025, 7, 18), date(2025, 7, 19)),
+            ("weekdays", date(2025, 7, 18), date(2025, 7, 21)),
+            ("every 2 weeks", date(2025, 7, 18), date(2025, 8, 1)),
+            ("FREQ=WEEKLY;BYDAY=MO,FR", date(2025, 7, 14), date(2025, 7, 18)),
+            ("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR", date(2025, 7, 18), date(2025, 7, 28)),
+            ("monthly", date(2025, 1, 31), date(2025, 2, 28)),
+            ("monthly on the 15th", date(2025, 7, 10), date(2025, 7, 15)),
+            ("monthly on the 15th", date(2025, 7, 15), date(2025, 8, 15)),
+            ("monthly on the 31st", date(2025, 8, 31), date(2025, 9, 30)),
+            ("yearly", date(2024, 2, 29), date(2025, 2, 28)),
+            ("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29", date(2027, 3, 1), date(2028, 2, 29)),
+            ("FREQ=YEARLY;BYMONTH=8", date(2025, 7, 18), date(2025, 8, 18)),
+        ];
+        for (rule, from, expected) in cases {
+            let recurrence: Recurrence = rule.parse().unwrap();
+            assert_eq!(recurrence.next_after(from), expected, "{rule} after {from}");
+        }
+    }
+
+    #[test]
+    fn test_anchored() {
+        // A due date on the 31st stays on the last day of shorter months
+        let monthly = "monthly".parse::<Recurrence>().unwrap().anchored(date(2025, 1, 31));
+        assert_eq!(monthly.to_string(), "FREQ=MONTHLY;BYMONTHDAY=31");
+        let mut due = date(2025, 1, 31);
+        let mut dues = Vec::new();
+        for _ in 0..3 {
+            due = monthly.next_after(due);
+            dues.push(due);
+        }
+        assert_eq!(dues, [date(2025, 2, 28), date(2025, 3, 31), date(2025, 4, 30)]);
+
+        let yearly = "yearly".parse::<Recurrence>().unwrap().anchored(date(2024, 2, 29));
+        assert_eq!(yearly.next_after(date(2025, 2, 28)), date(2026, 2, 28));
+        assert_eq!(yearly.next_after(date(2027, 2, 28)), date(2028, 2, 29));
+
+        // Days every month has need no pin
+        let monthly = "monthly".parse::<Recurrence>().unwrap();
+        assert_eq!(monthly.clone().anchored(date(2025, 1, 15)), monthly);
+    }
+}
+
+// endregion: --- Tests
@@ -0,0 +1,9 @@
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+
+#[derive(Debug, FromRow, Serialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+}
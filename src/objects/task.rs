@@ -1,6 +1,9 @@
+use crate::objects::Priority;
+use chrono::NaiveDate;
+use serde::Serialize;
 use sqlx::prelude::FromRow;
 
-#[derive(Debug, FromRow, Clone)]
+#[derive(Debug, Default, FromRow, Clone, Serialize)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct Task {
     pub id: i64,
@@ -10,5 +13,30 @@ pub struct Task {
     pub project_id: i64,
     pub parent_id: Option<i64>,
     pub sub_task_ids: sqlx::types::Json<Vec<i64>>,
+    pub due: Option<NaiveDate>,
+    pub scheduled: Option<NaiveDate>,
+    pub priority: Priority,
+    /// Recurrence rule in RRULE form, see [`crate::objects::Recurrence`]
+    pub recurrence: Option<String>,
+    /// Order of the task among its siblings of the same priority
+    pub position: i64,
+    /// Whether the subtasks are hidden in the TUI
+    pub folded: bool,
+    pub created: Option<NaiveDate>,
+    /// Day the task was last marked done
+    pub completed: Option<NaiveDate>,
+    /// Stable id of the task in other task managers and calendars
+    pub uuid: String,
+    /// Attributes of an imported task that twodo has no field for, written back on export
+    pub extra: Option<sqlx::types::Json<serde_json::Map<String, serde_json::Value>>>,
+    /// Names of the tags on the task, only populated by `read_task`
+    #[sqlx(default)]
+    pub tags: sqlx::types::Json<Vec<String>>,
 }
 
+impl Task {
+    /// A task is overdue when it is not done and its due date is before `today`
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        !self.done && self.due.is_some_and(|due| due < today)
+    }
+}
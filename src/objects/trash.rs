@@ -0,0 +1,14 @@
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+
+/// A deleted task or project
+#[derive(Debug, Clone, FromRow, Serialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct TrashItem {
+    /// Either `task` or `project`
+    pub kind: String,
+    pub id: i64,
+    /// Title of a task or name of a project
+    pub name: String,
+    pub deleted_at: String,
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+/// A named database, see `twodo workspace`
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct Workspace {
+    pub name: String,
+    pub path: String,
+    /// Whether this is the workspace used when none is given
+    pub current: bool,
+}
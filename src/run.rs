@@ -1,8 +1,17 @@
 use crate::Cli;
 use crate::Result;
+use crate::cli::Item;
+use crate::config;
 use crate::controller::delegater::delegate;
 use clap::Parser;
 
 pub async fn run() -> Result<()> {
-    delegate(Cli::parse()).await
+    // Loaded before parsing as it provides defaults of some arguments
+    let loaded = config::init();
+    let cli = Cli::parse();
+    // A broken config is only fatal outside of `twodo config`, which is how it gets fixed
+    if !matches!(cli.item, Some(Item::Config(_))) {
+        loaded?;
+    }
+    delegate(cli).await
 }
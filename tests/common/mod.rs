@@ -12,6 +12,6 @@ pub async fn init_db() -> Result<sqlx::SqlitePool> {
 
 pub async fn exec_cli(db: &sqlx::SqlitePool, args: Vec<&str>) -> Result<()> {
     let args = Cli::try_parse_from(args)?;
-    delegate_item(db, args.item.unwrap()).await?;
+    delegate_item(db, args.item.unwrap(), args.format).await?;
     Ok(())
 }